@@ -1,6 +1,13 @@
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use pngme::{
+    ChannelMask, ChunkPosition, FilterStrategy, FilterType, ImageEncodeOptions, InterlaceMethod,
+    LsbOptions, fragment,
+};
 use std::path::PathBuf;
 
+use crate::inspect::ChunkSelector;
+use crate::report::Format;
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
@@ -14,37 +21,154 @@ pub enum Commands {
     ///
     /// This adds a new chunk to your PNG that contains your message.
     /// Your image will look exactly the same but now carries hidden data.
+    /// By default the chunk is placed just before IEND so that decoders
+    /// and optimizers don't treat it as trailing garbage.
+    ///
+    /// With --method lsb the message goes into the pixels themselves
+    /// instead, so it survives tools that strip extra chunks. There is no
+    /// chunk type then: give the message with --text or --file.
     ///
     /// Example:
     ///   encode photo.png ruSt "Meet me at midnight"
+    ///   encode photo.png tEXt "Meet me at midnight" --keyword Comment
+    ///   encode photo.png iTXt "Rendez-vous à minuit" --keyword Comment --lang fr
+    ///   encode photo.png ruSt --file secret.pdf
+    ///   encode photo.png --method lsb --text "Meet me at midnight"
+    ///   encode photo.png --method lsb --key --file secret.txt
+    #[command(
+        group(ArgGroup::new("chunk_message").args(["message", "file"])),
+        group(ArgGroup::new("lsb_message").args(["text", "file"])),
+        group(
+            ArgGroup::new("chunk_options")
+                .args(["position", "index", "keyword", "lang", "split", "force"])
+                .multiple(true)
+                .requires("chunk_type")
+                .conflicts_with("text")
+        )
+    )]
     Encode {
         /// Path to the PNG image you want to hide a message in
         file_path: PathBuf,
 
         /// A 4-letter code that identifies your hidden chunk
         ///
-        /// Use any 4 letters like: ruSt, hide, note, data
+        /// Use any 4 letters like: ruSt, hiDe, noTe, daTa
         /// Tip: 'ruSt' is a good choice for secret messages
         ///
-        /// WARNING: Don't use standard chunk names like IDAT or IEND
-        chunk_type: String,
+        /// Standard names like IDAT, critical types (uppercase first letter),
+        /// public types (uppercase second letter) and types with a lowercase
+        /// third letter are refused unless --force is given.
+        #[arg(
+            required_unless_present = "method",
+            required_if_eq("method", "chunk"),
+            requires = "chunk_message"
+        )]
+        chunk_type: Option<String>,
 
         /// The secret message you want to hide
-        message: String,
+        message: Option<String>,
 
         /// Optional: Specify a custom output file path
         ///
-        /// If you don't provide this, a new file will be created with "_encode" suffix
-        /// Example: input.png becomes input_encode.png
+        /// If you don't provide this, a new file will be created with "_encoded" suffix
+        /// Example: input.png becomes input_encoded.png
         output_file: Option<PathBuf>,
+
+        /// Hide the contents of a file instead of a text message
+        ///
+        /// Any kind of file works (PDFs, keys, archives). Its name and size
+        /// are stored too, so decode can restore it exactly.
+        #[arg(long, conflicts_with_all = ["message", "keyword"])]
+        file: Option<PathBuf>,
+
+        /// Output PNG path (same as the positional output file; useful with --file)
+        #[arg(short, long, conflicts_with = "output_file")]
+        output: Option<PathBuf>,
+
+        /// Where to place the hidden chunk in the file
+        #[arg(long, value_enum, default_value_t = Position::BeforeIend)]
+        position: Position,
+
+        /// Insert the hidden chunk at an exact chunk index instead
+        ///
+        /// Index 0 is the first chunk after the PNG signature.
+        #[arg(long, conflicts_with = "position")]
+        index: Option<usize>,
+
+        /// Store the message as a standard text entry under this keyword
+        ///
+        /// Requires a text chunk type: tEXt, zTXt to compress long
+        /// messages, or iTXt for UTF-8 text. Other tools like exiftool and
+        /// image viewers can display these entries.
+        /// Keywords are 1-79 Latin-1 characters, e.g. Comment or Title.
+        #[arg(long)]
+        keyword: Option<String>,
+
+        /// Language of the message, as a tag like en or zh-TW (iTXt only)
+        #[arg(long, requires = "keyword")]
+        lang: Option<String>,
+
+        /// Encrypt the message with a passphrase
+        ///
+        /// You will be asked for the passphrase, or it can be supplied
+        /// through the PNGME_PASSPHRASE environment variable. The same
+        /// passphrase is needed to decode the message.
+        #[arg(long, conflicts_with = "keyword")]
+        encrypt: bool,
+
+        /// Split the data across several chunks of at most this many bytes
+        ///
+        /// Useful for large files, since many tools choke on huge chunks.
+        /// Each chunk spends 21 bytes on a fragment header, so the size must
+        /// be larger than that. Decode finds and joins all the pieces
+        /// automatically.
+        #[arg(
+            long,
+            value_name = "BYTES",
+            conflicts_with = "keyword",
+            value_parser = clap::value_parser!(u32).range(fragment::HEADER_SIZE as i64 + 1..)
+        )]
+        split: Option<u32>,
+
+        /// Allow chunk types that can break the image or clash with standard chunks
+        ///
+        /// Only useful for testing how decoders react to such chunks.
+        #[arg(long)]
+        force: bool,
+
+        /// Where to hide the message
+        #[arg(long, value_enum, default_value_t, requires_if("lsb", "lsb_message"))]
+        method: Method,
+
+        /// The secret message to hide with --method lsb
+        #[arg(long, conflicts_with_all = ["chunk_type", "file"])]
+        text: Option<String>,
+
+        #[command(flatten)]
+        lsb: LsbArgs,
     },
 
     /// Find and display a hidden message in a PNG file
     ///
     /// Searches for a specific chunk type and shows the message inside.
+    /// Encrypted messages are detected automatically and you will be
+    /// asked for the passphrase (or set PNGME_PASSPHRASE).
+    ///
+    /// Messages hidden with --method lsb are read from the pixels, using
+    /// the same --bits, --channels and --key as when encoding.
     ///
     /// Example:
     ///   decode photo.png ruSt
+    ///   decode photo.png tEXt --keyword Comment
+    ///   decode photo.png ruSt --all
+    ///   decode photo.png --method lsb
+    ///   decode photo.png --method lsb --key --bits 2
+    #[command(group(
+        ArgGroup::new("chunk_options")
+            .args(["keyword", "lang", "all", "index"])
+            .multiple(true)
+            .requires("chunk_type")
+    ))]
     Decode {
         /// Path to the PNG image to search
         file_path: PathBuf,
@@ -52,7 +176,43 @@ pub enum Commands {
         /// The 4-letter chunk code used when encoding
         ///
         /// This must match exactly what you used to hide the message.
-        chunk_type: String,
+        #[arg(required_unless_present = "method", required_if_eq("method", "chunk"))]
+        chunk_type: Option<String>,
+
+        /// Read the text entry stored under this keyword
+        ///
+        /// Requires a text chunk type such as tEXt, zTXt or iTXt.
+        /// Compressed entries are decompressed automatically.
+        #[arg(long)]
+        keyword: Option<String>,
+
+        /// Only match iTXt entries in this language, e.g. en or zh-TW
+        #[arg(long, requires = "keyword")]
+        lang: Option<String>,
+
+        /// Write the hidden data to this file instead of printing it
+        ///
+        /// Embedded files are restored byte for byte. Without this option
+        /// they are saved under their original name in the current directory.
+        #[arg(short, long, conflicts_with = "all")]
+        output: Option<PathBuf>,
+
+        /// Decode every matching chunk instead of only the first
+        ///
+        /// Split payloads are reassembled and shown once each.
+        #[arg(long, conflicts_with = "index")]
+        all: bool,
+
+        /// Decode only the chunk at this index, as listed by print
+        #[arg(long, value_name = "N")]
+        index: Option<usize>,
+
+        /// Where the message was hidden
+        #[arg(long, value_enum, default_value_t)]
+        method: Method,
+
+        #[command(flatten)]
+        lsb: LsbArgs,
     },
 
     /// Remove a hidden message chunk from a PNG file
@@ -62,12 +222,23 @@ pub enum Commands {
     ///
     /// Example:
     ///   remove photo.png ruSt
+    ///   remove photo.png ruSt --all
     Remove {
         /// Path to the PNG file to clean up
         file_path: PathBuf,
 
         /// The 4-letter chunk code to delete
         chunk_type: String,
+
+        /// Remove every chunk of this type instead of only the first
+        #[arg(long, conflicts_with = "index")]
+        all: bool,
+
+        /// Remove only the chunk at this index, as listed by print
+        ///
+        /// The chunk must have the given type.
+        #[arg(long, value_name = "N")]
+        index: Option<usize>,
     },
 
     /// Show all chunks in a PNG file (useful for exploration)
@@ -77,8 +248,280 @@ pub enum Commands {
     ///
     /// Example:
     ///   print photo.png
+    ///   print photo.png --format json
     Print {
         /// Path to the PNG file to analyze
         file_path: PathBuf,
+
+        /// Output format; json, yaml and csv include offsets, flags and CRCs
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
     },
+
+    /// Show everything about one chunk, down to the bytes
+    ///
+    /// Prints the chunk's offset in the file, what each letter of its type
+    /// means (critical, public, reserved, safe to copy), whether its CRC is
+    /// correct, the decoded fields of known chunk types, and a hexdump of
+    /// its data. Chunks with a wrong CRC can be inspected too.
+    ///
+    /// Example:
+    ///   inspect photo.png --chunk IHDR
+    ///   inspect photo.png --chunk 3 --limit 0
+    Inspect {
+        /// Path to the PNG file to read
+        file_path: PathBuf,
+
+        /// Chunk to show: an index as listed by print, or a type for the first of that type
+        #[arg(long, value_name = "TYPE|INDEX")]
+        chunk: ChunkSelector,
+
+        /// Dump at most this many data bytes; 0 dumps the whole chunk
+        #[arg(long, value_name = "BYTES", default_value_t = 256)]
+        limit: usize,
+    },
+
+    /// Check that a PNG file follows the PNG chunk layout rules
+    ///
+    /// Reports every structural problem found, such as a missing IHDR,
+    /// IEND not being last, or scattered IDAT chunks. Exits with an error
+    /// if any problem is found, so it can be used in scripts.
+    ///
+    /// Example:
+    ///   validate photo.png
+    Validate {
+        /// Path to the PNG file to check
+        file_path: PathBuf,
+    },
+
+    /// Repair wrong chunk CRCs, e.g. after editing a file by hand
+    ///
+    /// Lists every chunk whose CRC did not match, with the old and new
+    /// values, and saves the file with corrected CRCs (in place unless
+    /// --output is given).
+    ///
+    /// Example:
+    ///   fix-crc edited.png --dry-run
+    ///   fix-crc edited.png
+    FixCrc {
+        /// Path to the PNG file to repair
+        file_path: PathBuf,
+
+        /// Write the repaired file here instead of overwriting the input
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Only report wrong CRCs; do not write anything
+        #[arg(long, conflicts_with = "output")]
+        dry_run: bool,
+    },
+
+    /// Save the data hidden after the end of a PNG file
+    ///
+    /// Anything appended after the IEND chunk (a ZIP archive, another
+    /// file, junk) is ignored by image viewers. This copies it out.
+    ///
+    /// Example:
+    ///   extract-trailer photo.png
+    ///   extract-trailer photo.png -o appended.zip
+    ExtractTrailer {
+        /// Path to the PNG file to read
+        file_path: PathBuf,
+
+        /// Where to write the data [default: <name>_trailer.bin]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Delete the data appended after the end of a PNG file
+    ///
+    /// Keeps every chunk and removes only the bytes after IEND.
+    /// The file is changed in place unless --output is given.
+    ///
+    /// Example:
+    ///   strip-trailer photo.png
+    StripTrailer {
+        /// Path to the PNG file to clean up
+        file_path: PathBuf,
+
+        /// Write the cleaned file here instead of overwriting the input
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Re-encode the image data, e.g. to interlace a file or shrink it
+    ///
+    /// Decodes the pixels and stores them again with the chosen filters,
+    /// compression level and IDAT chunk size. The picture itself does not
+    /// change. The file is changed in place unless --output is given.
+    ///
+    /// Example:
+    ///   recompress photo.png
+    ///   recompress photo.png --interlace adam7 -o progressive.png
+    Recompress {
+        /// Path to the PNG file to re-encode
+        file_path: PathBuf,
+
+        /// Write the result here instead of overwriting the input
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Convert to this interlace method [default: keep the current one]
+        #[arg(long, value_enum)]
+        interlace: Option<Interlace>,
+
+        /// Filter to apply to every scanline
+        #[arg(long, value_enum, default_value_t = Filter::Adaptive)]
+        filter: Filter,
+
+        /// Compression level, from 0 (fastest) to 9 (smallest)
+        #[arg(long, default_value_t = 9, value_parser = clap::value_parser!(u32).range(0..=9))]
+        level: u32,
+
+        /// Largest size of each IDAT chunk
+        #[arg(
+            long,
+            value_name = "BYTES",
+            default_value_t = ImageEncodeOptions::DEFAULT_MAX_CHUNK_SIZE as u32,
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        max_chunk_size: u32,
+    },
+
+    /// Salvage the readable chunks of a damaged or truncated PNG file
+    ///
+    /// Chunks with a wrong CRC are kept, unreadable bytes are skipped up to
+    /// the next intact chunk, and a cut-off final chunk keeps whatever bytes
+    /// remain. Every problem found is reported. The salvaged chunks are
+    /// written with correct CRCs, so the new file can be decoded as usual.
+    ///
+    /// Example:
+    ///   recover damaged.png
+    ///   recover damaged.png -o fixed.png
+    Recover {
+        /// Path to the damaged PNG file
+        file_path: PathBuf,
+
+        /// Where to write the recovered file [default: <name>_recovered.png]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Ways of hiding a message.
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Method {
+    /// In a chunk of its own
+    #[default]
+    Chunk,
+    /// In the least significant bits of the pixels
+    Lsb,
+}
+
+/// Settings for `--method lsb`; decoding needs the same ones as encoding.
+///
+/// A chunk type is only given without `--method lsb`, so these conflict
+/// with it.
+#[derive(Args)]
+pub struct LsbArgs {
+    /// Low bits of each channel to use with --method lsb (1-8) [default: 1]
+    ///
+    /// More bits hold more data but change the image more.
+    #[arg(
+        long,
+        value_name = "N",
+        conflicts_with = "chunk_type",
+        value_parser = clap::value_parser!(u8).range(1..=8)
+    )]
+    pub bits: Option<u8>,
+
+    /// Channels to use with --method lsb [default: all but alpha]
+    ///
+    /// Any of r, g, b and a, plus y for the gray channel of grayscale images.
+    #[arg(long, value_name = "CHANNELS", conflicts_with = "chunk_type")]
+    pub channels: Option<ChannelMask>,
+
+    /// Scatter the bits with --method lsb in an order derived from a passphrase
+    ///
+    /// Without the passphrase the bits read as noise. It is read like for
+    /// --encrypt, and one passphrase serves both when they are combined.
+    #[arg(long, conflicts_with = "chunk_type")]
+    pub key: bool,
+}
+
+impl LsbArgs {
+    /// The LSB settings, with defaults for those not given.
+    pub fn options(&self) -> LsbOptions {
+        let options = LsbOptions::default()
+            .with_bits_per_channel(self.bits.unwrap_or(1))
+            .expect("clap checks the range");
+        match self.channels {
+            Some(channels) => options.with_channels(channels),
+            None => options,
+        }
+    }
+}
+
+/// Placement choices for a newly encoded chunk.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Position {
+    /// Just before IEND (recommended)
+    BeforeIend,
+    /// Right after IHDR
+    AfterIhdr,
+    /// Right before the first IDAT
+    BeforeIdat,
+}
+
+impl From<Position> for ChunkPosition {
+    fn from(position: Position) -> Self {
+        match position {
+            Position::BeforeIend => ChunkPosition::BeforeIend,
+            Position::AfterIhdr => ChunkPosition::AfterIhdr,
+            Position::BeforeIdat => ChunkPosition::BeforeFirstIdat,
+        }
+    }
+}
+
+/// Interlace methods for `recompress`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Interlace {
+    /// Rows top to bottom
+    None,
+    /// Seven Adam7 passes, for progressive display
+    Adam7,
+}
+
+impl From<Interlace> for InterlaceMethod {
+    fn from(interlace: Interlace) -> Self {
+        match interlace {
+            Interlace::None => InterlaceMethod::None,
+            Interlace::Adam7 => InterlaceMethod::Adam7,
+        }
+    }
+}
+
+/// Scanline filter choices for `recompress`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Filter {
+    /// Pick the best filter for each scanline (recommended)
+    Adaptive,
+    None,
+    Sub,
+    Up,
+    Average,
+    Paeth,
+}
+
+impl From<Filter> for FilterStrategy {
+    fn from(filter: Filter) -> Self {
+        match filter {
+            Filter::Adaptive => FilterStrategy::Adaptive,
+            Filter::None => FilterStrategy::Fixed(FilterType::None),
+            Filter::Sub => FilterStrategy::Fixed(FilterType::Sub),
+            Filter::Up => FilterStrategy::Fixed(FilterType::Up),
+            Filter::Average => FilterStrategy::Fixed(FilterType::Average),
+            Filter::Paeth => FilterStrategy::Fixed(FilterType::Paeth),
+        }
+    }
 }
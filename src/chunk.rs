@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::chunk_ref::ChunkRef;
 use crate::chunk_type::{ChunkType, ChunkTypeError};
 use crc::{CRC_32_ISO_HDLC, Crc};
 
@@ -139,6 +140,36 @@ impl Chunk {
         }
     }
 
+    /// Builds a chunk from fields read off the wire, verifying the stored CRC.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Chunk)` - If `crc` matches the checksum of the type and data
+    /// * `Err(ChunkError::CrcMismatch)` - Otherwise
+    pub(crate) fn from_parts(chunk_type: ChunkType, data: Vec<u8>, crc: u32) -> Result<Self> {
+        let expected_crc = Self::calculate_crc(&chunk_type, &data);
+        if crc != expected_crc {
+            return Err(ChunkError::CrcMismatch {
+                expected: expected_crc,
+                actual: crc,
+            });
+        }
+
+        Ok(Self::from_raw_parts(chunk_type, data, crc))
+    }
+
+    /// Builds a chunk from fields as read, keeping `crc` without checking it.
+    ///
+    /// Used for chunks whose CRC was already verified, and by lenient parsing
+    /// to keep chunks whose CRC is wrong.
+    pub(crate) fn from_raw_parts(chunk_type: ChunkType, data: Vec<u8>, crc: u32) -> Self {
+        Self {
+            chunk_type,
+            data,
+            crc,
+        }
+    }
+
     /// Calculates the CRC-32 checksum for a chunk type and data.
     ///
     /// The CRC is calculated over the concatenated bytes of the chunk type
@@ -202,8 +233,30 @@ impl Chunk {
         self.crc
     }
 
+    /// True if the stored CRC matches the chunk type and data.
+    ///
+    /// Always true for chunks built with `new` or parsed strictly; only
+    /// lenient parsing can produce chunks with a wrong CRC.
+    pub fn has_valid_crc(&self) -> bool {
+        self.crc == Self::calculate_crc(&self.chunk_type, &self.data)
+    }
+
+    /// Replaces the stored CRC with the one computed from the type and data.
+    ///
+    /// Returns the previous CRC.
+    pub fn recompute_crc(&mut self) -> u32 {
+        std::mem::replace(
+            &mut self.crc,
+            Self::calculate_crc(&self.chunk_type, &self.data),
+        )
+    }
+
     /// Attempts to interpret the chunk data as a UTF-8 string.
     ///
+    /// This treats the whole payload as text, which suits raw message chunks.
+    /// Standard text chunks have their own layout; decode them with
+    /// `TextChunk`, `ZtxtChunk` or `ItxtChunk` instead.
+    ///
     /// # Returns
     ///
     /// - `Ok(&str)`: The data as a valid UTF-8 string slice
@@ -278,71 +331,7 @@ impl TryFrom<&[u8]> for Chunk {
     type Error = ChunkError;
 
     fn try_from(bytes: &[u8]) -> Result<Self> {
-        // Parse the length field (first 4 bytes, big-endian)
-        let length_bytes: [u8; 4] = bytes
-            .get(..Self::LENGTH_SIZE)
-            .and_then(|slice| slice.try_into().ok())
-            .ok_or_else(|| ChunkError::NotEnoughBytes {
-                position: 0,
-                required: Self::LENGTH_SIZE,
-                actual: bytes.len() - 0,
-            })?;
-        let data_length = u32::from_be_bytes(length_bytes) as usize;
-
-        // Validate data length against PNG specification limit
-        if data_length > Self::MAX_DATA_SIZE {
-            return Err(ChunkError::TooLarge { size: data_length });
-        }
-
-        // Parse the chunk type (next 4 bytes)
-        let type_start = Self::LENGTH_SIZE;
-        let type_bytes: [u8; 4] = bytes
-            .get(type_start..type_start + Self::TYPE_SIZE)
-            .and_then(|slice| slice.try_into().ok())
-            .ok_or_else(|| ChunkError::NotEnoughBytes {
-                position: type_start,
-                required: Self::TYPE_SIZE,
-                actual: bytes.len() - type_start,
-            })?;
-        let chunk_type = ChunkType::try_from(type_bytes)?;
-
-        // Parse the data field (variable length)
-        let data_start = type_start + Self::TYPE_SIZE;
-        let data_bytes = bytes
-            .get(data_start..data_start + data_length)
-            .ok_or_else(|| ChunkError::NotEnoughBytes {
-                position: data_start,
-                required: data_length,
-                actual: bytes.len() - data_start,
-            })?
-            .to_vec();
-
-        // Parse the CRC field (last 4 bytes, big-endian)
-        let crc_start = data_start + data_length;
-        let crc_bytes: [u8; 4] = bytes
-            .get(crc_start..crc_start + Self::CRC_SIZE)
-            .and_then(|slice| slice.try_into().ok())
-            .ok_or_else(|| ChunkError::NotEnoughBytes {
-                position: crc_start,
-                required: Self::CRC_SIZE,
-                actual: bytes.len() - crc_start,
-            })?;
-        let crc = u32::from_be_bytes(crc_bytes);
-
-        // Verify CRC-32 checksum integrity
-        let expected_crc = Self::calculate_crc(&chunk_type, &data_bytes);
-        if crc != expected_crc {
-            return Err(ChunkError::CrcMismatch {
-                expected: expected_crc,
-                actual: crc,
-            });
-        }
-
-        Ok(Self {
-            chunk_type,
-            data: data_bytes,
-            crc,
-        })
+        ChunkRef::try_from(bytes).map(Chunk::from)
     }
 }
 
@@ -487,6 +476,16 @@ mod tests {
         assert!(chunk.is_err());
     }
 
+    #[test]
+    fn test_recompute_crc() {
+        let good = testing_chunk();
+        let mut chunk = Chunk::from_raw_parts(*good.chunk_type(), good.data().to_vec(), 1);
+        assert!(!chunk.has_valid_crc());
+        assert_eq!(chunk.recompute_crc(), 1);
+        assert!(chunk.has_valid_crc());
+        assert_eq!(chunk.crc(), good.crc());
+    }
+
     #[test]
     pub fn test_chunk_trait_impls() {
         let data_length: u32 = 42;
@@ -0,0 +1,206 @@
+use std::fmt;
+
+use crate::chunk::{Chunk, ChunkError, Result};
+use crate::chunk_type::ChunkType;
+
+/// A PNG chunk that borrows its data from the buffer it was parsed from.
+///
+/// `ChunkRef` has the same accessors as [`Chunk`] but never copies the data
+/// field, so scanning a large image (for example a memory-mapped file) costs
+/// no extra memory. Convert it with [`ChunkRef::to_chunk`] or `Chunk::from`
+/// when an owned, mutable chunk is needed.
+///
+/// # Examples
+///
+/// ```ignore
+/// let chunk = ChunkRef::try_from(&bytes[..])?;
+/// println!("{} ({} bytes)", chunk.chunk_type(), chunk.length());
+/// let owned: Chunk = chunk.to_chunk();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkRef<'a> {
+    chunk_type: ChunkType,
+    data: &'a [u8],
+    crc: u32,
+}
+
+impl<'a> ChunkRef<'a> {
+    /// Returns the length of the chunk's data field.
+    pub fn length(&self) -> u32 {
+        self.data.len() as u32
+    }
+
+    /// Returns a reference to the chunk's type.
+    pub fn chunk_type(&self) -> &ChunkType {
+        &self.chunk_type
+    }
+
+    /// Returns the chunk's data, borrowed from the parsed buffer.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the chunk's CRC-32 checksum.
+    pub fn crc(&self) -> u32 {
+        self.crc
+    }
+
+    /// Attempts to interpret the chunk data as a UTF-8 string.
+    pub fn data_as_str(&self) -> Result<&'a str> {
+        Ok(str::from_utf8(self.data)?)
+    }
+
+    /// Number of bytes the chunk occupies on the wire, including length, type and CRC.
+    pub fn size(&self) -> usize {
+        Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE + self.data.len() + Chunk::CRC_SIZE
+    }
+
+    /// Copies the chunk into an owned [`Chunk`].
+    pub fn to_chunk(&self) -> Chunk {
+        Chunk::from(*self)
+    }
+}
+
+impl From<ChunkRef<'_>> for Chunk {
+    fn from(chunk: ChunkRef<'_>) -> Self {
+        Chunk::from_raw_parts(chunk.chunk_type, chunk.data.to_vec(), chunk.crc)
+    }
+}
+
+/// Parses a chunk from the start of a byte slice without copying its data.
+///
+/// Checks are the same as for `Chunk::try_from`, including CRC verification.
+/// Bytes after the end of the chunk are ignored.
+impl<'a> TryFrom<&'a [u8]> for ChunkRef<'a> {
+    type Error = ChunkError;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self> {
+        // Parse the length field (first 4 bytes, big-endian)
+        let length_bytes: [u8; 4] = bytes
+            .get(..Chunk::LENGTH_SIZE)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(ChunkError::NotEnoughBytes {
+                position: 0,
+                required: Chunk::LENGTH_SIZE,
+                actual: bytes.len(),
+            })?;
+        let data_length = u32::from_be_bytes(length_bytes) as usize;
+
+        // Validate data length against PNG specification limit
+        if data_length > Chunk::MAX_DATA_SIZE {
+            return Err(ChunkError::TooLarge { size: data_length });
+        }
+
+        // Parse the chunk type (next 4 bytes)
+        let type_start = Chunk::LENGTH_SIZE;
+        let type_bytes: [u8; 4] = bytes
+            .get(type_start..type_start + Chunk::TYPE_SIZE)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or_else(|| ChunkError::NotEnoughBytes {
+                position: type_start,
+                required: Chunk::TYPE_SIZE,
+                actual: bytes.len() - type_start,
+            })?;
+        let chunk_type = ChunkType::try_from(type_bytes)?;
+
+        // Borrow the data field (variable length)
+        let data_start = type_start + Chunk::TYPE_SIZE;
+        let data = bytes
+            .get(data_start..data_start + data_length)
+            .ok_or_else(|| ChunkError::NotEnoughBytes {
+                position: data_start,
+                required: data_length,
+                actual: bytes.len() - data_start,
+            })?;
+
+        // Parse the CRC field (last 4 bytes, big-endian)
+        let crc_start = data_start + data_length;
+        let crc_bytes: [u8; 4] = bytes
+            .get(crc_start..crc_start + Chunk::CRC_SIZE)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or_else(|| ChunkError::NotEnoughBytes {
+                position: crc_start,
+                required: Chunk::CRC_SIZE,
+                actual: bytes.len() - crc_start,
+            })?;
+        let crc = u32::from_be_bytes(crc_bytes);
+
+        // Verify CRC-32 checksum integrity
+        let expected_crc = Chunk::calculate_crc(&chunk_type, data);
+        if crc != expected_crc {
+            return Err(ChunkError::CrcMismatch {
+                expected: expected_crc,
+                actual: crc,
+            });
+        }
+
+        Ok(Self {
+            chunk_type,
+            data,
+            crc,
+        })
+    }
+}
+
+impl fmt::Display for ChunkRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_chunk().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn testing_bytes() -> Vec<u8> {
+        Chunk::new(
+            ChunkType::from_str("RuSt").unwrap(),
+            b"This is where your secret message will be!".to_vec(),
+        )
+        .as_bytes()
+    }
+
+    #[test]
+    fn test_borrows_data() {
+        let bytes = testing_bytes();
+        let chunk = ChunkRef::try_from(&bytes[..]).unwrap();
+        assert_eq!(chunk.length(), 42);
+        assert_eq!(&chunk.chunk_type().to_string(), "RuSt");
+        assert_eq!(chunk.data().as_ptr(), bytes[8..].as_ptr());
+        assert_eq!(
+            chunk.data_as_str().unwrap(),
+            "This is where your secret message will be!"
+        );
+        assert_eq!(chunk.crc(), 2882656334);
+        assert_eq!(chunk.size(), bytes.len());
+    }
+
+    #[test]
+    fn test_to_chunk_matches_owned_parse() {
+        let bytes = testing_bytes();
+        let owned = ChunkRef::try_from(&bytes[..]).unwrap().to_chunk();
+        assert_eq!(owned.as_bytes(), bytes);
+        assert_eq!(owned.crc(), Chunk::try_from(&bytes[..]).unwrap().crc());
+    }
+
+    #[test]
+    fn test_invalid_crc() {
+        let mut bytes = testing_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        assert!(matches!(
+            ChunkRef::try_from(&bytes[..]),
+            Err(ChunkError::CrcMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_truncated() {
+        let bytes = testing_bytes();
+        assert!(matches!(
+            ChunkRef::try_from(&bytes[..20]),
+            Err(ChunkError::NotEnoughBytes { position: 8, .. })
+        ));
+    }
+}
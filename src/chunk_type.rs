@@ -12,6 +12,64 @@ pub struct ChunkType {
     bytes: [u8; 4],
 }
 
+/// The critical chunk types defined by the PNG specification.
+///
+/// Decoders must reject a file containing any other critical chunk.
+pub const CRITICAL_CHUNK_TYPES: [&str; 4] = ["IHDR", "PLTE", "IDAT", "IEND"];
+
+/// Every chunk type defined by the PNG specification, including APNG.
+pub const STANDARD_CHUNK_TYPES: [&str; 25] = [
+    "IHDR", "PLTE", "IDAT", "IEND", "cHRM", "cICP", "gAMA", "iCCP", "mDCV", "cLLI", "sBIT", "sRGB",
+    "bKGD", "hIST", "tRNS", "eXIf", "pHYs", "sPLT", "tIME", "iTXt", "tEXt", "zTXt", "acTL", "fcTL",
+    "fdAT",
+];
+
+/// The standard chunk types that hold keyword/text entries.
+pub const TEXT_CHUNK_TYPES: [&str; 3] = ["tEXt", "zTXt", "iTXt"];
+
+/// Why a chunk type is refused for hiding data.
+///
+/// Returned by [`ChunkType::check_hiding_policy`], in the order checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum TypePolicyError {
+    /// The type is defined by the PNG specification.
+    #[error("{0} is a standard PNG chunk type; hiding data in it can break the image")]
+    Standard(ChunkType),
+    /// The type is critical, so decoders refuse images they do not understand it in.
+    #[error(
+        "{0} is a critical chunk type (uppercase first letter); decoders will refuse the image"
+    )]
+    Critical(ChunkType),
+    /// The reserved bit (third letter lowercase) is set.
+    #[error("{0} has the reserved bit set (lowercase third letter), which makes the image invalid")]
+    ReservedBitSet(ChunkType),
+    /// The type is public, a namespace reserved for registered chunks.
+    #[error("{0} is a public chunk type (uppercase second letter), reserved for registered chunks")]
+    Public(ChunkType),
+}
+
+/// A chunk type property that makes conforming decoders reject the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeWarning {
+    /// The type is critical but is not one of [`CRITICAL_CHUNK_TYPES`].
+    UnknownCritical,
+    /// The reserved bit (third letter lowercase) is set.
+    ReservedBitSet,
+}
+
+impl fmt::Display for TypeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeWarning::UnknownCritical => {
+                write!(f, "unknown critical chunk; decoders will reject the file")
+            }
+            TypeWarning::ReservedBitSet => {
+                write!(f, "reserved bit set; decoders will reject the file")
+            }
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ChunkTypeError {
     #[error("Byte at index {index} (0x{byte:02X}) is not an ASCII alphabetic character")]
@@ -51,6 +109,61 @@ impl ChunkType {
         self.is_bit_set(3)
     }
 
+    /// True if the type is defined by the PNG specification.
+    pub fn is_standard(&self) -> bool {
+        STANDARD_CHUNK_TYPES
+            .iter()
+            .any(|known| known.as_bytes() == self.bytes)
+    }
+
+    /// True if the type is one of the critical types defined by the PNG specification.
+    pub fn is_known_critical(&self) -> bool {
+        CRITICAL_CHUNK_TYPES
+            .iter()
+            .any(|known| known.as_bytes() == self.bytes)
+    }
+
+    /// True if the type is one of the standard [`TEXT_CHUNK_TYPES`].
+    pub fn is_text(&self) -> bool {
+        TEXT_CHUNK_TYPES
+            .iter()
+            .any(|known| known.as_bytes() == self.bytes)
+    }
+
+    /// Checks that data can be hidden in chunks of this type without harm.
+    ///
+    /// Only private, ancillary types with the reserved bit clear pass, plus
+    /// the standard text types, which exist to carry keyword entries.
+    pub fn check_hiding_policy(&self) -> Result<(), TypePolicyError> {
+        if self.is_text() {
+            Ok(())
+        } else if self.is_standard() {
+            Err(TypePolicyError::Standard(*self))
+        } else if self.is_critical() {
+            Err(TypePolicyError::Critical(*self))
+        } else if !self.is_reserved_bit_valid() {
+            Err(TypePolicyError::ReservedBitSet(*self))
+        } else if self.is_public() {
+            Err(TypePolicyError::Public(*self))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Lists the properties of this type that make decoders reject a file containing it.
+    ///
+    /// An empty list means standard decoders will accept (or safely ignore) the chunk.
+    pub fn warnings(&self) -> Vec<TypeWarning> {
+        let mut warnings = Vec::new();
+        if self.is_critical() && !self.is_known_critical() {
+            warnings.push(TypeWarning::UnknownCritical);
+        }
+        if !self.is_reserved_bit_valid() {
+            warnings.push(TypeWarning::ReservedBitSet);
+        }
+        warnings
+    }
+
     /// Checks if the 5th bit (0x20) is set in the byte at the given index.
     #[inline]
     fn is_bit_set(&self, index: usize) -> bool {
@@ -194,6 +307,43 @@ mod tests {
         assert!(chunk.is_err());
     }
 
+    #[test]
+    pub fn test_chunk_type_is_standard() {
+        assert!(ChunkType::from_str("IDAT").unwrap().is_standard());
+        assert!(ChunkType::from_str("tEXt").unwrap().is_standard());
+        assert!(!ChunkType::from_str("ruSt").unwrap().is_standard());
+    }
+
+    #[test]
+    pub fn test_chunk_type_warnings() {
+        let warnings = |s: &str| ChunkType::from_str(s).unwrap().warnings();
+        assert!(warnings("IDAT").is_empty());
+        assert!(warnings("ruSt").is_empty());
+        assert_eq!(warnings("RuSt"), [TypeWarning::UnknownCritical]);
+        assert_eq!(warnings("rust"), [TypeWarning::ReservedBitSet]);
+        assert_eq!(
+            warnings("Rust"),
+            [TypeWarning::UnknownCritical, TypeWarning::ReservedBitSet]
+        );
+    }
+
+    #[test]
+    pub fn test_chunk_type_hiding_policy() {
+        let check = |s: &str| ChunkType::from_str(s).unwrap().check_hiding_policy();
+        assert_eq!(check("ruSt"), Ok(()));
+        for text in TEXT_CHUNK_TYPES {
+            assert_eq!(check(text), Ok(()));
+        }
+        assert!(matches!(check("IDAT"), Err(TypePolicyError::Standard(_))));
+        assert!(matches!(check("pHYs"), Err(TypePolicyError::Standard(_))));
+        assert!(matches!(check("RuSt"), Err(TypePolicyError::Critical(_))));
+        assert!(matches!(
+            check("ruse"),
+            Err(TypePolicyError::ReservedBitSet(_))
+        ));
+        assert!(matches!(check("rUSt"), Err(TypePolicyError::Public(_))));
+    }
+
     #[test]
     pub fn test_chunk_type_string() {
         let chunk = ChunkType::from_str("RuSt").unwrap();
@@ -1,8 +1,21 @@
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+use crate::inspect::{ChunkSelector, Inspection};
 use crate::png_file::{PngFile, PngFileError};
-use pngme::{Chunk, ChunkError, ChunkType, ChunkTypeError, PngError};
+use crate::report::{Format, PngReport, chunk_table};
+use pngme::{
+    Chunk, ChunkError, ChunkPosition, ChunkReader, ChunkType, ChunkTypeError, CrcFix, Diagnostic,
+    EncryptionError, FilePayload, Fragment, FragmentError, ImageEncodeOptions, ItxtChunk, LsbError,
+    LsbOptions, ParseOptions, ParseReport, PayloadError, Png, PngError, Reassembler, TextChunk,
+    TextError, TypePolicyError, Violation, ZtxtChunk, encryption, fragment, lsb, payload,
+};
+
+/// Environment variable that supplies the passphrase instead of prompting.
+pub const PASSPHRASE_ENV: &str = "PNGME_PASSPHRASE";
 
 pub type Result<T> = std::result::Result<T, CommandsError>;
 
@@ -18,24 +31,174 @@ pub enum CommandsError {
     Chunk(#[from] ChunkError),
     #[error("Chunk type error: {0}")]
     ChunkType(#[from] ChunkTypeError),
+    #[error("Text chunk error: {0}")]
+    Text(#[from] TextError),
+    #[error("Chunk type {0} does not support keywords; use tEXt, zTXt or iTXt")]
+    NotTextChunkType(String),
+    #[error("Chunk type {0} requires a keyword")]
+    KeywordRequired(String),
+    #[error("Chunk type {0} does not support language tags; use iTXt")]
+    LanguageRequiresItxt(String),
+    #[error("{0} (use --force to do it anyway)")]
+    ChunkTypePolicy(#[from] TypePolicyError),
     #[error("Chunk not found: {0}")]
     ChunkNotFound(String),
+    #[error("Chunk {index} is {actual}, not {expected}")]
+    ChunkTypeMismatch {
+        index: usize,
+        expected: String,
+        actual: String,
+    },
+    #[error("PNG structure is invalid: {0} violation(s) found")]
+    InvalidStructure(usize),
+    #[error("File is damaged beyond wrong CRCs ({0} problem(s)); use recover instead")]
+    Damaged(usize),
+    #[error("Encryption error: {0}")]
+    Encryption(EncryptionError),
+    #[error("Decryption failed: wrong passphrase or tampered data")]
+    DecryptionFailed,
+    #[error("Passphrases do not match")]
+    PassphraseMismatch,
+    #[error("Message is not valid UTF-8; use --output to save the raw bytes")]
+    InvalidUtf8(#[from] std::string::FromUtf8Error),
+    #[error("File payload error: {0}")]
+    Payload(#[from] PayloadError),
+    #[error("Files cannot be stored in text chunks")]
+    FileInTextChunk,
+    #[error(
+        "Split size {0} leaves no room for data after the {header}-byte fragment header",
+        header = fragment::HEADER_SIZE
+    )]
+    SplitTooSmall(usize),
+    #[error("Fragment error: {0}")]
+    Fragment(#[from] FragmentError),
+    #[error("Pixel data error: {0}")]
+    Lsb(#[from] LsbError),
+    #[error("No data after IEND")]
+    NoTrailer,
+    #[error("JSON output error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("YAML output error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("CSV output error: {0}")]
+    Csv(#[from] csv::Error),
+}
+
+impl From<EncryptionError> for CommandsError {
+    fn from(e: EncryptionError) -> Self {
+        match e {
+            EncryptionError::AuthenticationFailed => CommandsError::DecryptionFailed,
+            e => CommandsError::Encryption(e),
+        }
+    }
+}
+
+/// Identifies a text entry by keyword and, for iTXt, optionally by language tag.
+#[derive(Clone, Copy)]
+pub struct TextKey<'a> {
+    pub keyword: &'a str,
+    pub language_tag: Option<&'a str>,
+}
+
+/// The data to hide: a text message or the contents of a file.
+#[derive(Clone, Copy)]
+pub enum Message<'a> {
+    Text(&'a str),
+    File(&'a Path),
+}
+
+/// How and where `encode` stores the message.
+#[derive(Clone, Copy, Default)]
+pub struct EncodeOptions<'a> {
+    pub position: ChunkPosition,
+    pub text_key: Option<TextKey<'a>>,
+    pub encrypt: bool,
+    /// Split the payload into chunks of at most this many data bytes,
+    /// fragment header included
+    pub split: Option<usize>,
+    /// Skip the chunk type policy checks
+    pub force: bool,
+}
+
+/// Which of the chunks of a type `decode` and `remove` act on.
+#[derive(Clone, Copy, Default)]
+pub enum Selection {
+    /// The first matching chunk
+    #[default]
+    First,
+    /// Every matching chunk
+    All,
+    /// The chunk at this index in the file, as listed by `print`
+    Index(usize),
+}
+
+/// The result of `decode`: a message to show, or a file written to disk.
+pub enum Decoded {
+    Message(String),
+    Saved { path: PathBuf, size: usize },
+}
+
+/// A keyword/text entry decoded from any standard text chunk.
+struct TextEntry {
+    keyword: String,
+    language_tag: Option<String>,
+    text: String,
 }
 
 pub fn encode(
     file_path: impl AsRef<Path>,
     chunk_type: &str,
-    message: &str,
+    message: Message,
     output_file: Option<impl AsRef<Path>>,
+    options: &EncodeOptions,
 ) -> Result<()> {
     let mut png_file = PngFile::load(&file_path)?;
 
     // Create secret chunk and encode it into original file
-    let chunk = Chunk::new(
-        ChunkType::from_str(chunk_type)?,
-        message.as_bytes().to_vec(),
-    );
-    png_file.png_mut().append_chunk(chunk);
+    let chunk_type = ChunkType::from_str(chunk_type)?;
+    if !options.force {
+        chunk_type.check_hiding_policy()?;
+    }
+    let chunks = match (options.text_key, message) {
+        (Some(key), Message::Text(text)) => vec![text_chunk(chunk_type, key, text)?],
+        (Some(_), Message::File(_)) => return Err(CommandsError::FileInTextChunk),
+        (None, _) if chunk_type.is_text() => {
+            return Err(CommandsError::KeywordRequired(chunk_type.to_string()));
+        }
+        (None, message) => {
+            let passphrase = options.encrypt.then(|| read_passphrase(true)).transpose()?;
+            let data = payload_bytes(message, passphrase.as_deref())?;
+            match options.split {
+                Some(chunk_size) => {
+                    let max_size = chunk_size
+                        .checked_sub(fragment::HEADER_SIZE)
+                        .filter(|&size| size > 0)
+                        .ok_or(CommandsError::SplitTooSmall(chunk_size))?;
+                    let count = data.len().div_ceil(max_size);
+                    if count > fragment::MAX_FRAGMENTS as usize {
+                        let count = u32::try_from(count).unwrap_or(u32::MAX);
+                        return Err(FragmentError::TooManyFragments(count).into());
+                    }
+                    let payload_id = next_payload_id(png_file.png(), &chunk_type);
+                    fragment::split(&data, max_size, payload_id)
+                        .iter()
+                        .map(|f| Chunk::new(chunk_type, f.to_bytes()))
+                        .collect()
+                }
+                None => vec![Chunk::new(chunk_type, data)],
+            }
+        }
+    };
+
+    // Fragments are kept together, in order, at the requested position
+    let mut chunks = chunks.into_iter();
+    let first = chunks.next().expect("at least one chunk is created");
+    let index = png_file.png_mut().insert_chunk(options.position, first)?;
+    for (offset, chunk) in chunks.enumerate() {
+        png_file
+            .png_mut()
+            .insert_chunk_at(index + 1 + offset, chunk)?;
+    }
 
     // Write encoded file
     let output = output_file
@@ -45,31 +208,665 @@ pub fn encode(
     Ok(())
 }
 
-pub fn decode(file_path: impl AsRef<Path>, chunk_type: &str) -> Result<String> {
+/// Hides a message in the least significant bits of the image's pixels.
+///
+/// Unlike a chunk, the message survives tools that strip unknown chunks, as
+/// long as they keep the pixels exactly. The pixels are re-encoded with the
+/// file's own interlace method; every other chunk is kept.
+///
+/// With `keyed` the bits are scattered in an order derived from the
+/// passphrase. A single passphrase serves both that and `encrypt`.
+pub fn encode_lsb(
+    file_path: impl AsRef<Path>,
+    message: Message,
+    output_file: Option<impl AsRef<Path>>,
+    encrypt: bool,
+    keyed: bool,
+    lsb_options: &LsbOptions,
+) -> Result<()> {
+    let mut png_file = PngFile::load(&file_path)?;
+    let png = png_file.png_mut();
+    let mut image = png.image_data()?;
+    // Check the image suits LSB before asking for a passphrase
+    lsb::capacity(image.header(), lsb_options)?;
+
+    let passphrase = (encrypt || keyed)
+        .then(|| read_passphrase(true))
+        .transpose()?;
+    let data = payload_bytes(message, passphrase.as_deref().filter(|_| encrypt))?;
+    let lsb_options = with_passphrase_key(lsb_options, passphrase.as_deref().filter(|_| keyed));
+    lsb::embed(&mut image, &data, &lsb_options)?;
+    png.set_image_data(&image, &ImageEncodeOptions::default())?;
+
+    let output = output_file
+        .map(|p| p.as_ref().to_path_buf())
+        .unwrap_or_else(|| default_output_path(&file_path, "encoded"));
+    png_file.save(&output)?;
+    Ok(())
+}
+
+/// Serializes a message for hiding, encrypting it if given a passphrase.
+fn payload_bytes(message: Message, passphrase: Option<&str>) -> Result<Vec<u8>> {
+    let mut data = match message {
+        Message::Text(text) => text.as_bytes().to_vec(),
+        Message::File(path) => FilePayload::from_path(path)?.to_bytes(),
+    };
+    if let Some(passphrase) = passphrase {
+        data = encryption::encrypt(&data, passphrase)?;
+    }
+    Ok(data)
+}
+
+/// Returns `lsb_options` keyed with `passphrase`, if there is one.
+fn with_passphrase_key(lsb_options: &LsbOptions, passphrase: Option<&str>) -> LsbOptions {
+    match passphrase {
+        Some(passphrase) => lsb_options.clone().with_key(passphrase),
+        None => lsb_options.clone(),
+    }
+}
+
+/// Reads a message hidden in pixel bits by `encode_lsb`.
+///
+/// `lsb_options` and `keyed` must match the ones used to hide it. Encrypted
+/// messages and files are handled as by `decode`; a keyed message is
+/// decrypted with the same passphrase.
+pub fn decode_lsb(
+    file_path: impl AsRef<Path>,
+    lsb_options: &LsbOptions,
+    keyed: bool,
+    output: Option<&Path>,
+) -> Result<Decoded> {
     let png_file = PngFile::load(&file_path)?;
+    let image = png_file.png().image_data()?;
+    let passphrase = keyed.then(|| read_passphrase(false)).transpose()?;
+    let lsb_options = with_passphrase_key(lsb_options, passphrase.as_deref());
+    let mut data = lsb::extract(&image, &lsb_options)?;
+    if encryption::is_encrypted(&data) {
+        let passphrase = match passphrase {
+            Some(passphrase) => passphrase,
+            None => read_passphrase(false)?,
+        };
+        data = encryption::decrypt(&data, &passphrase)?;
+    }
+    write_decoded(data, output)
+}
 
-    Ok(png_file
-        .png()
-        .chunk_by_type(chunk_type)
-        .ok_or_else(|| CommandsError::ChunkNotFound(chunk_type.to_string()))?
-        .data_as_str()?
-        .to_string())
+/// Finds hidden messages and returns them, or writes them to disk.
+///
+/// `selection` picks the first match, every match, or one chunk by index.
+/// The first match is found by streaming the file, which stops reading as soon
+/// as the message is complete.
+/// Split payloads are reassembled (once per payload) and encrypted chunks
+/// are decrypted first. Embedded files are written to
+/// `output`, or to their original file name in the current directory
+/// (never overwriting an existing file). Text is written to `output` if given.
+pub fn decode(
+    file_path: impl AsRef<Path>,
+    chunk_type: &str,
+    text_key: Option<TextKey>,
+    selection: Selection,
+    output: Option<&Path>,
+) -> Result<Vec<Decoded>> {
+    let c_type = ChunkType::from_str(chunk_type)?;
+    let is_text = text_key.is_some() || c_type.is_text();
+    if is_text {
+        check_text_key(&c_type, text_key)?;
+    }
+    let payloads = match selection {
+        Selection::First => find_first(file_path.as_ref(), &c_type, is_text, text_key)?
+            .into_iter()
+            .collect(),
+        _ => {
+            let png_file = PngFile::load(&file_path)?;
+            find_selected(png_file.png(), &c_type, is_text, text_key, selection)?
+        }
+    };
+
+    if payloads.is_empty() {
+        return Err(CommandsError::ChunkNotFound(describe(&c_type, text_key)));
+    }
+
+    // Ask for the passphrase at most once, however many payloads are encrypted
+    let mut passphrase = None;
+    payloads
+        .into_iter()
+        .map(|data| {
+            let data = if encryption::is_encrypted(&data) {
+                let passphrase = match &passphrase {
+                    Some(passphrase) => passphrase,
+                    None => passphrase.insert(read_passphrase(false)?),
+                };
+                encryption::decrypt(&data, passphrase)?
+            } else {
+                data
+            };
+            write_decoded(data, output)
+        })
+        .collect()
 }
 
-pub fn remove(file_path: impl AsRef<Path>, chunk_type: &str) -> Result<()> {
+/// Streams the file and returns the first matching payload, without reading past it.
+///
+/// A split payload is read up to its last fragment.
+fn find_first(
+    file_path: &Path,
+    chunk_type: &ChunkType,
+    is_text: bool,
+    text_key: Option<TextKey>,
+) -> Result<Option<Vec<u8>>> {
+    let reader = ChunkReader::new(BufReader::new(File::open(file_path)?))?;
+    let mut reassembler = Reassembler::new();
+    for chunk in reader {
+        let chunk = chunk?;
+        if chunk.chunk_type() != chunk_type {
+            continue;
+        }
+        if is_text {
+            let Some(entry) = read_text_entry(&chunk, text_key)? else {
+                continue;
+            };
+            if text_key.is_none_or(|k| k.matches(&entry)) {
+                return Ok(Some(entry.text.into_bytes()));
+            }
+        } else if fragment::is_fragment(chunk.data()) {
+            let fragment = Fragment::try_from(chunk.data())?;
+            if reassembler
+                .payload_id()
+                .is_some_and(|id| id != fragment.payload_id())
+            {
+                continue;
+            }
+            reassembler.push(fragment)?;
+            if reassembler.is_complete() {
+                return Ok(Some(reassembler.finish()?));
+            }
+        } else if reassembler.payload_id().is_none() {
+            return Ok(Some(chunk.data().to_vec()));
+        }
+    }
+
+    // Reached the end with only part of a split payload
+    if reassembler.payload_id().is_some() {
+        reassembler.finish()?;
+    }
+    Ok(None)
+}
+
+/// Collects the payloads of every selected chunk from a loaded PNG.
+///
+/// Each split payload is reassembled once, however many of its fragments are selected.
+fn find_selected(
+    png: &Png,
+    chunk_type: &ChunkType,
+    is_text: bool,
+    text_key: Option<TextKey>,
+    selection: Selection,
+) -> Result<Vec<Vec<u8>>> {
+    let mut payloads = Vec::new();
+    let mut seen_payloads = HashSet::new();
+    for index in select_chunks(png, chunk_type, selection)? {
+        let chunk = &png.chunks()[index];
+        if is_text {
+            let Some(entry) = read_text_entry(chunk, text_key)? else {
+                continue;
+            };
+            if text_key.is_none_or(|k| k.matches(&entry)) {
+                payloads.push(entry.text.into_bytes());
+            }
+        } else if fragment::is_fragment(chunk.data()) {
+            let first = Fragment::try_from(chunk.data())?;
+            if seen_payloads.insert(first.payload_id()) {
+                payloads.push(reassemble(png, chunk_type, first)?);
+            }
+        } else {
+            payloads.push(chunk.data().to_vec());
+        }
+    }
+    Ok(payloads)
+}
+
+/// Turns decoded bytes into a message, or saves them to disk.
+fn write_decoded(data: Vec<u8>, output: Option<&Path>) -> Result<Decoded> {
+    if payload::is_file_payload(&data) {
+        let file = FilePayload::try_from(&data[..])?;
+        let path = match output {
+            Some(path) => {
+                fs::write(path, file.data())?;
+                path.to_path_buf()
+            }
+            None => {
+                let path = PathBuf::from(file.file_name());
+                OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&path)?
+                    .write_all(file.data())?;
+                path
+            }
+        };
+        return Ok(Decoded::Saved {
+            path,
+            size: file.data().len(),
+        });
+    }
+
+    match output {
+        Some(path) => {
+            fs::write(path, &data)?;
+            Ok(Decoded::Saved {
+                path: path.to_path_buf(),
+                size: data.len(),
+            })
+        }
+        None => Ok(Decoded::Message(String::from_utf8(data)?)),
+    }
+}
+
+/// Removes the selected chunks of a type and saves the file in place.
+///
+/// Returns the number of chunks removed.
+pub fn remove(
+    file_path: impl AsRef<Path>,
+    chunk_type: &str,
+    selection: Selection,
+) -> Result<usize> {
     let mut png_file = PngFile::load(&file_path)?;
 
-    png_file.png_mut().remove_first_chunk(chunk_type)?;
-    png_file.save(&file_path)?;
+    let removed = match selection {
+        Selection::First => {
+            png_file.png_mut().remove_first_chunk(chunk_type)?;
+            1
+        }
+        Selection::All => png_file.png_mut().remove_all_chunks(chunk_type)?.len(),
+        Selection::Index(index) => {
+            let c_type = ChunkType::from_str(chunk_type)?;
+            select_chunks(png_file.png(), &c_type, selection)?;
+            png_file.png_mut().remove_chunk_at(index)?;
+            1
+        }
+    };
+    png_file.save(png_file.path())?;
+    Ok(removed)
+}
+
+/// Describes the file on stdout, as a listing or in a machine-readable format.
+pub fn print(file_path: &Path, format: Format) -> Result<()> {
+    let png_file = PngFile::load(file_path)?;
+    let report = || PngReport::new(file_path, png_file.png());
+    match format {
+        Format::Text => {}
+        Format::Json => {
+            serde_json::to_writer_pretty(io::stdout().lock(), &report())?;
+            println!();
+            return Ok(());
+        }
+        Format::Yaml => {
+            serde_yaml::to_writer(io::stdout().lock(), &report())?;
+            return Ok(());
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(io::stdout().lock());
+            for chunk in report().chunks {
+                writer.serialize(chunk)?;
+            }
+            writer.flush()?;
+            return Ok(());
+        }
+    }
+
+    match png_file.png().header_info() {
+        Ok(ihdr) => println!("Image: {ihdr}"),
+        Err(e) => println!("Image: unavailable ({e})"),
+    }
+    println!("Number of chunks: {}", png_file.png().chunks().len());
+    print!("{}", chunk_table(&report().chunks));
+
+    for (index, chunk) in png_file.png().chunks().iter().enumerate() {
+        if chunk.chunk_type().bytes() != *b"zTXt" {
+            continue;
+        }
+        match ZtxtChunk::try_from(chunk) {
+            Ok(ztxt) => println!(
+                "Chunk {index}: zTXt '{}' ({} bytes compressed, {} bytes uncompressed)",
+                ztxt.keyword(),
+                ztxt.compressed_len(),
+                ztxt.uncompressed_len()
+            ),
+            Err(e) => println!("Chunk {index}: zTXt could not be decoded ({e})"),
+        }
+    }
+
+    let trailer = png_file.png().trailer();
+    if !trailer.is_empty() {
+        println!("Trailer: {} bytes after IEND", trailer.len());
+    }
     Ok(())
 }
 
-pub fn print(file_path: &Path) -> Result<()> {
+/// Writes the bytes that follow IEND to a file.
+///
+/// Defaults to `<name>_trailer.bin` next to the input. Returns the path and size.
+pub fn extract_trailer(
+    file_path: impl AsRef<Path>,
+    output: Option<&Path>,
+) -> Result<(PathBuf, usize)> {
+    let png_file = PngFile::load(&file_path)?;
+    let trailer = png_file.png().trailer();
+    if trailer.is_empty() {
+        return Err(CommandsError::NoTrailer);
+    }
+
+    let path = output
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| default_output_path(&file_path, "trailer").with_extension("bin"));
+    fs::write(&path, trailer)?;
+    Ok((path, trailer.len()))
+}
+
+/// Removes the bytes that follow IEND and saves the file.
+///
+/// The file is saved to `output`, or in place. Returns the number of bytes removed.
+pub fn strip_trailer(file_path: impl AsRef<Path>, output: Option<&Path>) -> Result<usize> {
+    let mut png_file = PngFile::load(&file_path)?;
+    let removed = png_file.png_mut().take_trailer().len();
+    if removed == 0 {
+        return Err(CommandsError::NoTrailer);
+    }
+    png_file.save(output.unwrap_or(png_file.path()))?;
+    Ok(removed)
+}
+
+/// Renders a detailed view of one chunk: hexdump, property bits, CRC status and fields.
+///
+/// The file is read leniently so chunks with a wrong CRC can still be inspected.
+/// A type selects the first chunk of that type.
+pub fn inspect(file_path: &Path, selector: ChunkSelector, limit: Option<usize>) -> Result<String> {
+    let bytes = fs::read(file_path)?;
+    let ParseReport { png, offsets, .. } = Png::parse_with(&bytes, ParseOptions::lenient())?;
+    let index = match selector {
+        ChunkSelector::Index(index) if index < png.chunks().len() => index,
+        ChunkSelector::Index(index) => {
+            return Err(PngError::IndexOutOfBounds {
+                index,
+                len: png.chunks().len(),
+            }
+            .into());
+        }
+        ChunkSelector::Type(chunk_type) => png
+            .chunks()
+            .iter()
+            .position(|chunk| *chunk.chunk_type() == chunk_type)
+            .ok_or_else(|| CommandsError::ChunkNotFound(chunk_type.to_string()))?,
+    };
+
+    let inspection = Inspection {
+        index,
+        offset: offsets[index],
+        chunk: &png.chunks()[index],
+        limit,
+    };
+    Ok(inspection.to_string())
+}
+
+pub fn validate(file_path: &Path) -> Result<Vec<Violation>> {
     let png_file = PngFile::load(file_path)?;
-    println!("{}", png_file.png());
+    Ok(png_file.png().validate())
+}
+
+/// Finds chunks with a wrong CRC and rewrites their CRCs.
+///
+/// The fixed file is written to `output`, or back to `file_path`. Nothing is
+/// written with `dry_run`, or when every CRC is already correct. Damage other
+/// than wrong CRCs is refused; `recover` handles that.
+pub fn fix_crc(
+    file_path: impl AsRef<Path>,
+    output: Option<&Path>,
+    dry_run: bool,
+) -> Result<Vec<CrcFix>> {
+    let bytes = fs::read(&file_path)?;
+    let ParseReport {
+        mut png,
+        diagnostics,
+        ..
+    } = Png::parse_with(&bytes, ParseOptions::lenient())?;
+    let damage = diagnostics
+        .iter()
+        .filter(|d| !matches!(d, Diagnostic::CrcMismatch { .. }))
+        .count();
+    if damage > 0 {
+        return Err(CommandsError::Damaged(damage));
+    }
+
+    let fixes = png.recompute_crcs();
+    if !dry_run && !fixes.is_empty() {
+        let path = output.unwrap_or(file_path.as_ref());
+        png.write_to(BufWriter::new(File::create(path)?))?;
+    }
+    Ok(fixes)
+}
+
+/// Decodes the pixels and stores them again as `options` asks.
+///
+/// The file is saved to `output`, or in place. Returns the size of the
+/// compressed image data before and after.
+pub fn recompress(
+    file_path: impl AsRef<Path>,
+    output: Option<&Path>,
+    options: &ImageEncodeOptions,
+) -> Result<(usize, usize)> {
+    let mut png_file = PngFile::load(&file_path)?;
+    let png = png_file.png_mut();
+    let before = png.compressed_image_data().len();
+    let image = png.image_data()?;
+    png.set_image_data(&image, options)?;
+    let after = png.compressed_image_data().len();
+    png_file.save(output.unwrap_or(png_file.path()))?;
+    Ok((before, after))
+}
+
+/// What `recover` salvaged from a damaged file.
+pub struct Recovery {
+    pub diagnostics: Vec<Diagnostic>,
+    pub chunks: usize,
+    pub path: PathBuf,
+}
+
+/// Parses a damaged file leniently and writes the salvaged chunks to a new file.
+///
+/// Wrong CRCs are recomputed so the result loads normally.
+pub fn recover(file_path: impl AsRef<Path>, output: Option<&Path>) -> Result<Recovery> {
+    let bytes = fs::read(&file_path)?;
+    let mut report = Png::parse_with(&bytes, ParseOptions::lenient())?;
+    let png = &mut report.png;
+    png.recompute_crcs();
+
+    let path = output
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| default_output_path(&file_path, "recovered"));
+    png.write_to(BufWriter::new(File::create(&path)?))?;
+    Ok(Recovery {
+        diagnostics: report.diagnostics,
+        chunks: png.chunks().len(),
+        path,
+    })
+}
+
+/// Picks a payload id not used by any fragment already stored in chunks of this type.
+fn next_payload_id(png: &Png, chunk_type: &ChunkType) -> u32 {
+    png.chunks()
+        .iter()
+        .filter(|c| c.chunk_type() == chunk_type)
+        .filter_map(|c| Fragment::try_from(c.data()).ok())
+        .map(|f| f.payload_id().wrapping_add(1))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Collects every fragment of `first`'s payload from chunks of this type and joins them.
+///
+/// Fragments belonging to other payloads are ignored.
+fn reassemble(png: &Png, chunk_type: &ChunkType, first: Fragment) -> Result<Vec<u8>> {
+    let payload_id = first.payload_id();
+    let mut reassembler = Reassembler::new();
+    for chunk in png.chunks().iter().filter(|c| c.chunk_type() == chunk_type) {
+        if !fragment::is_fragment(chunk.data()) {
+            continue;
+        }
+        let fragment = Fragment::try_from(chunk.data())?;
+        if fragment.payload_id() == payload_id {
+            reassembler.push(fragment)?;
+        }
+    }
+    Ok(reassembler.finish()?)
+}
+
+/// Reads the passphrase from `PNGME_PASSPHRASE`, or prompts for it on the terminal.
+///
+/// When `confirm` is set the prompt asks twice and fails if the entries differ.
+fn read_passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+
+    let passphrase = rpassword::prompt_password("Passphrase: ")?;
+    if confirm && rpassword::prompt_password("Confirm passphrase: ")? != passphrase {
+        return Err(CommandsError::PassphraseMismatch);
+    }
+    Ok(passphrase)
+}
+
+/// Builds a standard text chunk of the requested type holding `message` under `key`.
+fn text_chunk(chunk_type: ChunkType, key: TextKey, message: &str) -> Result<Chunk> {
+    match (&chunk_type.bytes(), key.language_tag) {
+        (b"iTXt", language_tag) => Ok(ItxtChunk::new(key.keyword, message)?
+            .with_language(language_tag.unwrap_or_default(), "")?
+            .to_chunk()),
+        (_, Some(_)) if chunk_type.is_text() => {
+            Err(CommandsError::LanguageRequiresItxt(chunk_type.to_string()))
+        }
+        (b"tEXt", None) => Ok(TextChunk::new(key.keyword, message)?.to_chunk()),
+        (b"zTXt", None) => Ok(ZtxtChunk::new(key.keyword, message)?.to_chunk()),
+        _ => Err(CommandsError::NotTextChunkType(chunk_type.to_string())),
+    }
+}
+
+/// Decodes a text chunk for a search, or `None` if it should be skipped.
+///
+/// With a keyword filter a broken entry is skipped, since a later one may
+/// still match; without one the first entry is the answer, so its error is too.
+fn read_text_entry(chunk: &Chunk, text_key: Option<TextKey>) -> Result<Option<TextEntry>> {
+    match read_text(chunk) {
+        Ok(entry) => Ok(Some(entry)),
+        Err(_) if text_key.is_some() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Decodes any standard text chunk into a keyword/text entry.
+fn read_text(chunk: &Chunk) -> Result<TextEntry> {
+    let entry = match &chunk.chunk_type().bytes() {
+        b"tEXt" => {
+            let text = TextChunk::try_from(chunk)?;
+            TextEntry {
+                keyword: text.keyword().to_string(),
+                language_tag: None,
+                text: text.text().to_string(),
+            }
+        }
+        b"zTXt" => {
+            let ztxt = ZtxtChunk::try_from(chunk)?;
+            TextEntry {
+                keyword: ztxt.keyword().to_string(),
+                language_tag: None,
+                text: ztxt.text().to_string(),
+            }
+        }
+        b"iTXt" => {
+            let itxt = ItxtChunk::try_from(chunk)?;
+            TextEntry {
+                keyword: itxt.keyword().to_string(),
+                language_tag: Some(itxt.language_tag().to_string()),
+                text: itxt.text().to_string(),
+            }
+        }
+        _ => {
+            return Err(CommandsError::NotTextChunkType(
+                chunk.chunk_type().to_string(),
+            ));
+        }
+    };
+    Ok(entry)
+}
+
+impl TextKey<'_> {
+    /// True if `entry` has this keyword and, when one is given, this language.
+    ///
+    /// Language tags are compared case-insensitively.
+    fn matches(&self, entry: &TextEntry) -> bool {
+        self.keyword == entry.keyword
+            && self.language_tag.is_none_or(|tag| {
+                entry
+                    .language_tag
+                    .as_deref()
+                    .is_some_and(|t| t.eq_ignore_ascii_case(tag))
+            })
+    }
+}
+
+/// Checks that `key` can be looked up in chunks of this type.
+fn check_text_key(chunk_type: &ChunkType, key: Option<TextKey>) -> Result<()> {
+    if !chunk_type.is_text() {
+        return Err(CommandsError::NotTextChunkType(chunk_type.to_string()));
+    }
+    if key.is_some_and(|k| k.language_tag.is_some()) && chunk_type.bytes() != *b"iTXt" {
+        return Err(CommandsError::LanguageRequiresItxt(chunk_type.to_string()));
+    }
     Ok(())
 }
 
+/// Returns the indices of the chunks of this type picked by `selection`.
+///
+/// `First` and `All` both yield every chunk of the type; callers stop early
+/// for `First` once something matches. An index must point at a chunk of
+/// the requested type.
+fn select_chunks(png: &Png, chunk_type: &ChunkType, selection: Selection) -> Result<Vec<usize>> {
+    match selection {
+        Selection::First | Selection::All => Ok(png
+            .chunks()
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.chunk_type() == chunk_type)
+            .map(|(index, _)| index)
+            .collect()),
+        Selection::Index(index) => {
+            let chunk = png.chunks().get(index).ok_or(PngError::IndexOutOfBounds {
+                index,
+                len: png.chunks().len(),
+            })?;
+            if chunk.chunk_type() != chunk_type {
+                return Err(CommandsError::ChunkTypeMismatch {
+                    index,
+                    expected: chunk_type.to_string(),
+                    actual: chunk.chunk_type().to_string(),
+                });
+            }
+            Ok(vec![index])
+        }
+    }
+}
+
+/// Describes what was searched for, for "not found" messages.
+fn describe(chunk_type: &ChunkType, key: Option<TextKey>) -> String {
+    match key {
+        Some(TextKey {
+            keyword,
+            language_tag: Some(tag),
+        }) => format!("{chunk_type} with keyword '{keyword}' and language '{tag}'"),
+        Some(TextKey { keyword, .. }) => format!("{chunk_type} with keyword '{keyword}'"),
+        None => chunk_type.to_string(),
+    }
+}
+
 pub fn default_output_path(input_path: impl AsRef<Path>, suffix: &str) -> PathBuf {
     let input_path = input_path.as_ref();
     let parent = input_path.parent().unwrap_or_else(|| Path::new("."));
@@ -84,3 +881,40 @@ pub fn default_output_path(input_path: impl AsRef<Path>, suffix: &str) -> PathBu
     );
     parent.join(new_name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A PNG with a malformed tEXt entry before a valid one.
+    fn png_with_broken_text() -> Png {
+        let text = ChunkType::from_str("tEXt").unwrap();
+        Png::from_chunks(vec![
+            Chunk::new(text, b"Comment".to_vec()),
+            TextChunk::new("Comment", "found").unwrap().to_chunk(),
+            Chunk::new(ChunkType::from_str("IEND").unwrap(), Vec::new()),
+        ])
+    }
+
+    #[test]
+    fn test_keyword_search_skips_broken_entries() {
+        let png = png_with_broken_text();
+        let text = ChunkType::from_str("tEXt").unwrap();
+        let key = TextKey {
+            keyword: "Comment",
+            language_tag: None,
+        };
+
+        let found = find_selected(&png, &text, true, Some(key), Selection::All).unwrap();
+        assert_eq!(found, [b"found".to_vec()]);
+
+        let path = std::env::temp_dir().join(format!("pngme-text-{}.png", std::process::id()));
+        fs::write(&path, png.as_bytes()).unwrap();
+        let first = find_first(&path, &text, true, Some(key));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(first.unwrap(), Some(b"found".to_vec()));
+
+        // Without a keyword the broken first entry is still reported
+        assert!(find_selected(&png, &text, true, None, Selection::First).is_err());
+    }
+}
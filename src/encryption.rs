@@ -0,0 +1,282 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+/// Specialized `Result` type for encryption operations.
+pub type Result<T> = std::result::Result<T, EncryptionError>;
+
+/// Magic bytes marking a payload produced by [`encrypt`].
+pub const MAGIC: [u8; 7] = *b"PNGMENC";
+
+/// Current version of the encrypted payload format.
+pub const VERSION: u8 = 1;
+
+/// Size of the random salt fed to the key derivation function.
+pub const SALT_SIZE: usize = 16;
+
+/// Size of the ChaCha20-Poly1305 nonce.
+pub const NONCE_SIZE: usize = 12;
+
+/// Size of the Poly1305 authentication tag appended to the ciphertext.
+pub const TAG_SIZE: usize = 16;
+
+/// Largest Argon2 memory cost in KiB accepted from a payload header (256 MiB).
+pub const MAX_MEMORY_COST: u32 = 256 * 1024;
+
+/// Largest Argon2 iteration count accepted from a payload header.
+pub const MAX_TIME_COST: u32 = 16;
+
+/// Largest Argon2 parallelism accepted from a payload header.
+pub const MAX_PARALLELISM: u32 = 16;
+
+/// Size of the version 1 header: magic, version, Argon2 costs, salt and nonce.
+pub const HEADER_SIZE: usize = MAGIC.len() + 1 + 3 * 4 + SALT_SIZE + NONCE_SIZE;
+
+/// Errors that can occur when encrypting or decrypting a payload.
+#[derive(Debug, thiserror::Error)]
+pub enum EncryptionError {
+    /// Returned when the payload does not start with the encryption magic bytes.
+    #[error("Payload is not encrypted")]
+    NotEncrypted,
+
+    /// Returned when the payload was written by an unknown format version.
+    #[error("Unsupported encryption format version {0}")]
+    UnsupportedVersion(u8),
+
+    /// Returned when the payload is too short to hold the header and tag.
+    #[error("Encrypted payload is truncated: need at least {required} bytes, got {actual}")]
+    Truncated { required: usize, actual: usize },
+
+    /// Returned when the stored key derivation parameters are rejected.
+    #[error("Key derivation failed: {0}")]
+    KeyDerivation(argon2::Error),
+
+    /// Returned when the header asks for more key derivation work than
+    /// [`MAX_MEMORY_COST`], [`MAX_TIME_COST`] or [`MAX_PARALLELISM`] allow.
+    #[error(
+        "Key derivation costs too high: {memory} KiB, {iterations} iterations, {parallelism} lanes"
+    )]
+    ExcessiveCost {
+        memory: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+
+    /// Returned when authentication fails: the passphrase is wrong or the
+    /// payload has been tampered with.
+    #[error("Decryption failed: wrong passphrase or tampered data")]
+    AuthenticationFailed,
+}
+
+/// Encrypts a payload with a key derived from `passphrase`.
+///
+/// The key is derived with Argon2id from the passphrase and a random salt, and
+/// the payload is sealed with ChaCha20-Poly1305. The output is laid out as:
+/// - 7 bytes: Magic "PNGMENC"
+/// - 1 byte: Format version (1)
+/// - 12 bytes: Argon2 memory cost, iterations and parallelism (big-endian u32 each)
+/// - 16 bytes: Salt
+/// - 12 bytes: Nonce
+/// - N + 16 bytes: Ciphertext followed by the authentication tag
+///
+/// The whole header is authenticated as associated data, so tampering with
+/// any byte causes decryption to fail.
+///
+/// # Example
+///
+/// ```ignore
+/// let sealed = encryption::encrypt(b"Meet me at midnight", "hunter2")?;
+/// let chunk = Chunk::new(chunk_type, sealed);
+/// ```
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let params = Params::default();
+    let mut salt = [0u8; SALT_SIZE];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let mut output = Vec::with_capacity(HEADER_SIZE + plaintext.len() + TAG_SIZE);
+    output.extend_from_slice(&MAGIC);
+    output.push(VERSION);
+    output.extend_from_slice(&params.m_cost().to_be_bytes());
+    output.extend_from_slice(&params.t_cost().to_be_bytes());
+    output.extend_from_slice(&params.p_cost().to_be_bytes());
+    output.extend_from_slice(&salt);
+    output.extend_from_slice(&nonce);
+
+    let key = derive_key(passphrase, &salt, params)?;
+    let ciphertext = ChaCha20Poly1305::new(&key)
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad: &output,
+            },
+        )
+        .expect("encryption into a Vec cannot fail");
+    output.extend(ciphertext);
+    Ok(output)
+}
+
+/// Decrypts a payload produced by [`encrypt`].
+///
+/// # Returns
+///
+/// - `Ok(Vec<u8>)`: The original plaintext
+/// - `Err(EncryptionError)`:
+///   - `NotEncrypted`: The payload has no encryption header
+///   - `UnsupportedVersion`: The payload uses an unknown format version
+///   - `Truncated`: The payload is too short
+///   - `ExcessiveCost`: The header asks for an unreasonably expensive key derivation
+///   - `AuthenticationFailed`: Wrong passphrase or tampered data
+pub fn decrypt(payload: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    if !is_encrypted(payload) {
+        return Err(EncryptionError::NotEncrypted);
+    }
+    let version = payload[MAGIC.len()];
+    if version != VERSION {
+        return Err(EncryptionError::UnsupportedVersion(version));
+    }
+    if payload.len() < HEADER_SIZE + TAG_SIZE {
+        return Err(EncryptionError::Truncated {
+            required: HEADER_SIZE + TAG_SIZE,
+            actual: payload.len(),
+        });
+    }
+
+    let (header, ciphertext) = payload.split_at(HEADER_SIZE);
+    let read_u32 = |offset: usize| {
+        u32::from_be_bytes(
+            header[offset..offset + 4]
+                .try_into()
+                .expect("slice is exactly 4 bytes"),
+        )
+    };
+    let costs_start = MAGIC.len() + 1;
+    let (memory, iterations, parallelism) = (
+        read_u32(costs_start),
+        read_u32(costs_start + 4),
+        read_u32(costs_start + 8),
+    );
+    // The header is untrusted until authenticated, which needs the key first
+    if memory > MAX_MEMORY_COST || iterations > MAX_TIME_COST || parallelism > MAX_PARALLELISM {
+        return Err(EncryptionError::ExcessiveCost {
+            memory,
+            iterations,
+            parallelism,
+        });
+    }
+    let params = Params::new(memory, iterations, parallelism, None)
+        .map_err(EncryptionError::KeyDerivation)?;
+    let salt_start = costs_start + 12;
+    let salt = &header[salt_start..salt_start + SALT_SIZE];
+    let nonce = Nonce::from_slice(&header[salt_start + SALT_SIZE..]);
+
+    let key = derive_key(passphrase, salt, params)?;
+    ChaCha20Poly1305::new(&key)
+        .decrypt(
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map_err(|_| EncryptionError::AuthenticationFailed)
+}
+
+/// True if the payload starts with the encryption header magic and a version byte.
+pub fn is_encrypted(payload: &[u8]) -> bool {
+    payload.len() > MAGIC.len() && payload.starts_with(&MAGIC)
+}
+
+/// Derives a 256-bit key from a passphrase with Argon2id.
+fn derive_key(passphrase: &str, salt: &[u8], params: Params) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(EncryptionError::KeyDerivation)?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let sealed = encrypt(b"Meet me at midnight", "correct horse").unwrap();
+        assert!(is_encrypted(&sealed));
+        assert_eq!(sealed.len(), HEADER_SIZE + 19 + TAG_SIZE);
+        assert_eq!(
+            decrypt(&sealed, "correct horse").unwrap(),
+            b"Meet me at midnight"
+        );
+    }
+
+    #[test]
+    fn test_decrypt_wrong_passphrase() {
+        let sealed = encrypt(b"secret", "correct horse").unwrap();
+        assert!(matches!(
+            decrypt(&sealed, "battery staple"),
+            Err(EncryptionError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_tampered() {
+        let mut sealed = encrypt(b"secret", "correct horse").unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(matches!(
+            decrypt(&sealed, "correct horse"),
+            Err(EncryptionError::AuthenticationFailed)
+        ));
+
+        let mut sealed = encrypt(b"secret", "correct horse").unwrap();
+        sealed[HEADER_SIZE - 1] ^= 1;
+        assert!(matches!(
+            decrypt(&sealed, "correct horse"),
+            Err(EncryptionError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_invalid_header() {
+        assert!(matches!(
+            decrypt(b"plain message", "pw"),
+            Err(EncryptionError::NotEncrypted)
+        ));
+
+        let mut sealed = encrypt(b"secret", "pw").unwrap();
+        sealed[MAGIC.len()] = 2;
+        assert!(matches!(
+            decrypt(&sealed, "pw"),
+            Err(EncryptionError::UnsupportedVersion(2))
+        ));
+
+        assert!(matches!(
+            decrypt(&sealed[..HEADER_SIZE], "pw"),
+            Err(EncryptionError::UnsupportedVersion(2))
+        ));
+        let sealed = encrypt(b"secret", "pw").unwrap();
+        assert!(matches!(
+            decrypt(&sealed[..HEADER_SIZE], "pw"),
+            Err(EncryptionError::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_refuses_excessive_costs() {
+        let sealed = encrypt(b"secret", "pw").unwrap();
+        let costs = MAGIC.len() + 1;
+        for (offset, value) in [(0, u32::MAX), (4, u32::MAX), (8, MAX_PARALLELISM + 1)] {
+            let mut tampered = sealed.clone();
+            tampered[costs + offset..costs + offset + 4].copy_from_slice(&value.to_be_bytes());
+            // Running the KDF with these costs would exhaust memory or hang
+            assert!(matches!(
+                decrypt(&tampered, "pw"),
+                Err(EncryptionError::ExcessiveCost { .. })
+            ));
+        }
+    }
+}
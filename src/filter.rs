@@ -0,0 +1,258 @@
+use std::fmt;
+
+/// Per-scanline filter types defined by PNG filter method 0.
+///
+/// Each scanline in the decompressed image data starts with one byte naming
+/// the filter applied to it. Filters predict each byte from its neighbours:
+/// `a` is the corresponding byte of the pixel to the left, `b` the byte
+/// above, and `c` the byte above and to the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterType {
+    /// The byte is stored unchanged
+    None = 0,
+    /// Difference from `a`
+    Sub = 1,
+    /// Difference from `b`
+    Up = 2,
+    /// Difference from the mean of `a` and `b`
+    Average = 3,
+    /// Difference from whichever of `a`, `b` or `c` is closest to `a + b - c`
+    Paeth = 4,
+}
+
+impl FilterType {
+    /// All filter types, in order of their type byte.
+    pub const ALL: [FilterType; 5] = [
+        FilterType::None,
+        FilterType::Sub,
+        FilterType::Up,
+        FilterType::Average,
+        FilterType::Paeth,
+    ];
+}
+
+impl TryFrom<u8> for FilterType {
+    type Error = u8;
+
+    /// Returns the unknown byte as the error.
+    fn try_from(value: u8) -> Result<Self, u8> {
+        FilterType::ALL.get(value as usize).copied().ok_or(value)
+    }
+}
+
+impl fmt::Display for FilterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FilterType::None => "None",
+            FilterType::Sub => "Sub",
+            FilterType::Up => "Up",
+            FilterType::Average => "Average",
+            FilterType::Paeth => "Paeth",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// How to choose the filter for each scanline when encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterStrategy {
+    /// Use the same filter for every scanline
+    Fixed(FilterType),
+    /// Try every filter and keep the one with the smallest sum of absolute
+    /// differences, the heuristic recommended by the PNG specification
+    ///
+    /// Palette images and bit depths below 8 always use `None`, which the
+    /// specification recommends since filtering rarely helps them.
+    #[default]
+    Adaptive,
+}
+
+/// Reverses `filter` on one scanline in place.
+///
+/// `previous` is the already unfiltered scanline above, or `None` for the
+/// first row, where the row above counts as all zeros. `bytes_per_pixel` is
+/// the filter distance from `Ihdr::bytes_per_pixel`.
+pub fn unfilter(
+    filter: FilterType,
+    row: &mut [u8],
+    previous: Option<&[u8]>,
+    bytes_per_pixel: usize,
+) {
+    if filter == FilterType::None {
+        return;
+    }
+    for i in 0..row.len() {
+        let (left, above, upper_left) = neighbours(row, previous, bytes_per_pixel, i);
+        row[i] = row[i].wrapping_add(predict(filter, left, above, upper_left));
+    }
+}
+
+/// Applies `filter` to one scanline, writing the result to `out`.
+///
+/// `row` and `previous` are unfiltered; `out` must be as long as `row` and
+/// does not include the filter-type byte.
+pub fn filter(
+    filter: FilterType,
+    row: &[u8],
+    previous: Option<&[u8]>,
+    bytes_per_pixel: usize,
+    out: &mut [u8],
+) {
+    for (i, byte) in out.iter_mut().enumerate() {
+        let (left, above, upper_left) = neighbours(row, previous, bytes_per_pixel, i);
+        *byte = row[i].wrapping_sub(predict(filter, left, above, upper_left));
+    }
+}
+
+/// Picks the filter with the smallest sum of absolute differences for one scanline.
+///
+/// Filtered bytes are read as signed values, so both 1 and 255 count as
+/// small. Returns the filter and writes the filtered row to `out`.
+pub fn choose_filter(
+    row: &[u8],
+    previous: Option<&[u8]>,
+    bytes_per_pixel: usize,
+    out: &mut [u8],
+) -> FilterType {
+    let mut candidate = vec![0; row.len()];
+    let mut best = (FilterType::None, u64::MAX);
+    for filter_type in FilterType::ALL {
+        filter(filter_type, row, previous, bytes_per_pixel, &mut candidate);
+        let sum = candidate
+            .iter()
+            .map(|&b| (b as i8).unsigned_abs() as u64)
+            .sum();
+        if sum < best.1 {
+            best = (filter_type, sum);
+            out.copy_from_slice(&candidate);
+        }
+    }
+    best.0
+}
+
+/// The bytes `a` (left), `b` (above) and `c` (above left) for position `i`.
+///
+/// Positions left of the first pixel, and the row above the first row, count as zero.
+fn neighbours(
+    row: &[u8],
+    previous: Option<&[u8]>,
+    bytes_per_pixel: usize,
+    i: usize,
+) -> (u8, u8, u8) {
+    let above = |i: usize| previous.map_or(0, |p| p[i]);
+    if i >= bytes_per_pixel {
+        (
+            row[i - bytes_per_pixel],
+            above(i),
+            above(i - bytes_per_pixel),
+        )
+    } else {
+        (0, above(i), 0)
+    }
+}
+
+/// The value `filter` predicts for a byte from its neighbours.
+fn predict(filter: FilterType, a: u8, b: u8, c: u8) -> u8 {
+    match filter {
+        FilterType::None => 0,
+        FilterType::Sub => a,
+        FilterType::Up => b,
+        FilterType::Average => ((a as u16 + b as u16) / 2) as u8,
+        FilterType::Paeth => paeth(a, b, c),
+    }
+}
+
+/// The Paeth predictor: whichever of `a`, `b`, `c` is closest to `a + b - c`.
+///
+/// Ties are broken in the order `a`, `b`, `c`, as the specification requires.
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let pa = (p - a as i16).abs();
+    let pb = (p - b as i16).abs();
+    let pc = (p - c as i16).abs();
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_type_from_byte() {
+        assert_eq!(FilterType::try_from(4), Ok(FilterType::Paeth));
+        assert_eq!(FilterType::try_from(5), Err(5));
+    }
+
+    #[test]
+    fn test_unfilter_sub() {
+        let mut row = [1, 2, 3, 4, 5, 6];
+        unfilter(FilterType::Sub, &mut row, None, 2);
+        assert_eq!(row, [1, 2, 4, 6, 9, 12]);
+    }
+
+    #[test]
+    fn test_unfilter_up() {
+        let mut row = [1, 2, 255];
+        unfilter(FilterType::Up, &mut row, Some(&[10, 20, 2]), 1);
+        assert_eq!(row, [11, 22, 1]);
+
+        let mut first = [1, 2, 3];
+        unfilter(FilterType::Up, &mut first, None, 1);
+        assert_eq!(first, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_unfilter_average() {
+        let mut row = [1, 1, 1];
+        unfilter(FilterType::Average, &mut row, Some(&[10, 20, 30]), 1);
+        // 1 + 10/2, 1 + (6 + 20)/2, 1 + (14 + 30)/2
+        assert_eq!(row, [6, 14, 23]);
+    }
+
+    #[test]
+    fn test_unfilter_paeth() {
+        let mut row = [5, 5, 5];
+        unfilter(FilterType::Paeth, &mut row, Some(&[10, 20, 30]), 1);
+        // First byte predicts from b alone; later bytes pick the closest neighbour
+        assert_eq!(row, [15, 25, 35]);
+    }
+
+    #[test]
+    fn test_filter_round_trip() {
+        let previous = [200, 3, 17, 90, 255, 0];
+        let row = [1, 250, 33, 7, 128, 64];
+        for filter_type in FilterType::ALL {
+            let mut filtered = [0; 6];
+            filter(filter_type, &row, Some(&previous), 3, &mut filtered);
+            unfilter(filter_type, &mut filtered, Some(&previous), 3);
+            assert_eq!(filtered, row, "{filter_type}");
+        }
+    }
+
+    #[test]
+    fn test_choose_filter_prefers_smallest_residuals() {
+        // A horizontal gradient is flat under Sub
+        let row = [10, 20, 30, 40, 50, 60];
+        let mut out = [0; 6];
+        assert_eq!(choose_filter(&row, None, 1, &mut out), FilterType::Sub);
+        assert_eq!(out, [10, 10, 10, 10, 10, 10]);
+
+        // A row identical to the one above is all zeros under Up
+        assert_eq!(choose_filter(&row, Some(&row), 1, &mut out), FilterType::Up);
+        assert_eq!(out, [0; 6]);
+    }
+
+    #[test]
+    fn test_paeth_tie_break() {
+        assert_eq!(paeth(5, 5, 0), 5);
+        assert_eq!(paeth(20, 10, 10), 20);
+        assert_eq!(paeth(10, 20, 10), 20);
+        assert_eq!(paeth(10, 20, 15), 15);
+    }
+}
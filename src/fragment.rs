@@ -0,0 +1,455 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Specialized `Result` type for fragment operations.
+pub type Result<T> = std::result::Result<T, FragmentError>;
+
+/// Magic bytes marking a payload fragment.
+pub const MAGIC: [u8; 8] = *b"PNGMFRAG";
+
+/// Current version of the fragment format.
+pub const VERSION: u8 = 1;
+
+/// Size of the fragment header: magic, version, payload id, index and total.
+pub const HEADER_SIZE: usize = MAGIC.len() + 1 + 3 * 4;
+
+/// Largest fragment count a payload may be split into.
+///
+/// The total is read from untrusted data, so fragments claiming more are
+/// refused rather than tracked.
+pub const MAX_FRAGMENTS: u32 = 1 << 20;
+
+/// Most missing index ranges listed in a [`FragmentError::Missing`] message.
+const MAX_LISTED_RANGES: usize = 10;
+
+/// One numbered piece of a payload that was split across several chunks.
+///
+/// Serialized fragments are laid out as:
+/// - 8 bytes: Magic "PNGMFRAG"
+/// - 1 byte: Format version (1)
+/// - 4 bytes: Payload id (big-endian u32), shared by all fragments of a payload
+/// - 4 bytes: Fragment index, starting at 0 (big-endian u32)
+/// - 4 bytes: Total number of fragments (big-endian u32)
+/// - N bytes: Fragment data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    payload_id: u32,
+    index: u32,
+    total: u32,
+    data: Vec<u8>,
+}
+
+/// Errors that can occur when parsing or reassembling fragments.
+#[derive(Debug, thiserror::Error)]
+pub enum FragmentError {
+    /// Returned when the bytes do not start with the fragment magic.
+    #[error("Data is not a payload fragment")]
+    NotAFragment,
+
+    /// Returned when the fragment was written by an unknown format version.
+    #[error("Unsupported fragment version {0}")]
+    UnsupportedVersion(u8),
+
+    /// Returned when the fragment is shorter than its header.
+    #[error("Fragment is truncated")]
+    Truncated,
+
+    /// Returned when a fragment's index is not below its total, or the total is zero.
+    #[error("Fragment {index} is out of range for a payload of {total} fragments")]
+    IndexOutOfRange { index: u32, total: u32 },
+
+    /// Returned when a fragment claims more than [`MAX_FRAGMENTS`] fragments.
+    #[error("Payload of {0} fragments exceeds the limit of {MAX_FRAGMENTS}")]
+    TooManyFragments(u32),
+
+    /// Returned when a fragment belongs to a different payload than the others.
+    #[error("Fragment belongs to payload {actual}, expected payload {expected}")]
+    WrongPayload { expected: u32, actual: u32 },
+
+    /// Returned when fragments of one payload disagree on the total count.
+    #[error("Fragment total mismatch: expected {expected}, got {actual}")]
+    TotalMismatch { expected: u32, actual: u32 },
+
+    /// Returned when the same fragment index is seen twice.
+    #[error("Payload {payload_id} has duplicate fragment {index}")]
+    Duplicate { payload_id: u32, index: u32 },
+
+    /// Returned when fragments are absent at reassembly time.
+    ///
+    /// `missing` holds the absent indices as ascending, non-overlapping ranges.
+    #[error("Payload {payload_id} is missing fragment(s) {} of {total}", MissingList(.missing))]
+    Missing {
+        payload_id: u32,
+        total: u32,
+        missing: Vec<RangeInclusive<u32>>,
+    },
+
+    /// Returned when there are no fragments at all to reassemble.
+    #[error("No fragments to reassemble")]
+    Empty,
+}
+
+impl Fragment {
+    /// The id shared by every fragment of the same payload.
+    pub fn payload_id(&self) -> u32 {
+        self.payload_id
+    }
+
+    /// Position of this fragment within the payload, starting at 0.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Total number of fragments the payload was split into.
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// The slice of the payload carried by this fragment.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Serializes the fragment with its header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.data.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.payload_id.to_be_bytes());
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&self.total.to_be_bytes());
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+}
+
+/// Attempts to parse a fragment from its serialized form.
+impl TryFrom<&[u8]> for Fragment {
+    type Error = FragmentError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        let rest = bytes
+            .strip_prefix(&MAGIC)
+            .ok_or(FragmentError::NotAFragment)?;
+        let (&version, rest) = rest.split_first().ok_or(FragmentError::Truncated)?;
+        if version != VERSION {
+            return Err(FragmentError::UnsupportedVersion(version));
+        }
+
+        let (fields, data) = rest
+            .split_first_chunk::<12>()
+            .ok_or(FragmentError::Truncated)?;
+        let read_u32 = |i: usize| u32::from_be_bytes(fields[i..i + 4].try_into().expect("4 bytes"));
+        let (payload_id, index, total) = (read_u32(0), read_u32(4), read_u32(8));
+        if total > MAX_FRAGMENTS {
+            return Err(FragmentError::TooManyFragments(total));
+        }
+        if index >= total {
+            return Err(FragmentError::IndexOutOfRange { index, total });
+        }
+
+        Ok(Self {
+            payload_id,
+            index,
+            total,
+            data: data.to_vec(),
+        })
+    }
+}
+
+/// True if the bytes start with the fragment magic.
+pub fn is_fragment(bytes: &[u8]) -> bool {
+    bytes.starts_with(&MAGIC)
+}
+
+/// Splits a payload into fragments carrying at most `max_data_size` bytes each.
+///
+/// An empty payload still produces one (empty) fragment so that it can be
+/// found and reassembled. Callers should keep the count within
+/// [`MAX_FRAGMENTS`], or the fragments will be refused when parsed.
+///
+/// # Panics
+///
+/// Panics if `max_data_size` is zero.
+pub fn split(payload: &[u8], max_data_size: usize, payload_id: u32) -> Vec<Fragment> {
+    assert!(max_data_size > 0, "fragment size must be non-zero");
+    let pieces: Vec<&[u8]> = if payload.is_empty() {
+        vec![payload]
+    } else {
+        payload.chunks(max_data_size).collect()
+    };
+    let total = pieces.len() as u32;
+
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(index, data)| Fragment {
+            payload_id,
+            index: index as u32,
+            total,
+            data: data.to_vec(),
+        })
+        .collect()
+}
+
+/// Collects the fragments of one payload and joins them back together.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut reassembler = Reassembler::new();
+/// for fragment in fragments {
+///     reassembler.push(fragment)?;
+/// }
+/// let payload = reassembler.finish()?;
+/// ```
+#[derive(Debug, Default)]
+pub struct Reassembler {
+    payload: Option<(u32, u32)>,
+    fragments: BTreeMap<u32, Vec<u8>>,
+}
+
+impl Reassembler {
+    /// Creates an empty reassembler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of the payload being reassembled, once the first fragment is pushed.
+    pub fn payload_id(&self) -> Option<u32> {
+        self.payload.map(|(id, _)| id)
+    }
+
+    /// Adds a fragment.
+    ///
+    /// The first fragment fixes the payload id and total; later fragments must agree.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the fragment was accepted
+    /// * `Err(FragmentError)` - If it belongs to another payload, disagrees on
+    ///   the total, or repeats an index already seen
+    pub fn push(&mut self, fragment: Fragment) -> Result<()> {
+        let (payload_id, total) = *self
+            .payload
+            .get_or_insert((fragment.payload_id, fragment.total));
+        if fragment.payload_id != payload_id {
+            return Err(FragmentError::WrongPayload {
+                expected: payload_id,
+                actual: fragment.payload_id,
+            });
+        }
+        if fragment.total != total {
+            return Err(FragmentError::TotalMismatch {
+                expected: total,
+                actual: fragment.total,
+            });
+        }
+        if self.fragments.contains_key(&fragment.index) {
+            return Err(FragmentError::Duplicate {
+                payload_id,
+                index: fragment.index,
+            });
+        }
+
+        self.fragments.insert(fragment.index, fragment.data);
+        Ok(())
+    }
+
+    /// True once every fragment of the payload has been pushed.
+    pub fn is_complete(&self) -> bool {
+        self.payload
+            .is_some_and(|(_, total)| self.fragments.len() as u64 == total as u64)
+    }
+
+    /// Indices of the fragments not yet pushed, as ascending ranges.
+    ///
+    /// Costs time in the number of fragments pushed, not the claimed total.
+    pub fn missing(&self) -> Vec<RangeInclusive<u32>> {
+        let total = self.payload.map_or(0, |(_, total)| total);
+        let mut ranges = Vec::new();
+        let mut next = 0;
+        for &index in self.fragments.keys() {
+            if index > next {
+                ranges.push(next..=index - 1);
+            }
+            next = index + 1;
+        }
+        if next < total {
+            ranges.push(next..=total - 1);
+        }
+        ranges
+    }
+
+    /// Joins the fragments in index order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u8>)` - The original payload
+    /// * `Err(FragmentError::Missing)` - Listing every absent fragment index
+    /// * `Err(FragmentError::Empty)` - If no fragment was pushed
+    pub fn finish(self) -> Result<Vec<u8>> {
+        let (payload_id, total) = self.payload.ok_or(FragmentError::Empty)?;
+        if !self.is_complete() {
+            return Err(FragmentError::Missing {
+                payload_id,
+                total,
+                missing: self.missing(),
+            });
+        }
+        Ok(self.fragments.into_values().flatten().collect())
+    }
+}
+
+/// Formats index ranges as a comma-separated list such as `2, 5-7`.
+///
+/// Only the first [`MAX_LISTED_RANGES`] ranges are written.
+struct MissingList<'a>(&'a [RangeInclusive<u32>]);
+
+impl fmt::Display for MissingList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, range) in self.0.iter().take(MAX_LISTED_RANGES).enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match (range.start(), range.end()) {
+                (start, end) if start == end => write!(f, "{start}")?,
+                (start, end) => write!(f, "{start}-{end}")?,
+            }
+        }
+        if self.0.len() > MAX_LISTED_RANGES {
+            write!(f, " and {} more range(s)", self.0.len() - MAX_LISTED_RANGES)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_reassemble() {
+        let payload: Vec<u8> = (0..100).collect();
+        let fragments = split(&payload, 30, 7);
+        assert_eq!(fragments.len(), 4);
+        assert!(
+            fragments
+                .iter()
+                .all(|f| f.total() == 4 && f.payload_id() == 7)
+        );
+        assert_eq!(fragments[3].data().len(), 10);
+
+        let mut reassembler = Reassembler::new();
+        for fragment in fragments.into_iter().rev() {
+            let bytes = fragment.to_bytes();
+            assert!(is_fragment(&bytes));
+            reassembler
+                .push(Fragment::try_from(&bytes[..]).unwrap())
+                .unwrap();
+        }
+        assert_eq!(reassembler.finish().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_reassemble_missing() {
+        let payload = vec![0u8; 100];
+        let mut reassembler = Reassembler::new();
+        for fragment in split(&payload, 10, 1) {
+            if ![2, 5, 9].contains(&fragment.index()) {
+                reassembler.push(fragment).unwrap();
+            }
+        }
+        assert_eq!(reassembler.missing(), vec![2..=2, 5..=5, 9..=9]);
+        assert!(!reassembler.is_complete());
+
+        let err = reassembler.finish().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Payload 1 is missing fragment(s) 2, 5, 9 of 10"
+        );
+    }
+
+    #[test]
+    fn test_missing_ranges_are_summarized() {
+        let payload = vec![0u8; 100];
+        let mut reassembler = Reassembler::new();
+        for fragment in split(&payload, 1, 1) {
+            if fragment.index() % 4 == 1 {
+                reassembler.push(fragment).unwrap();
+            }
+        }
+        let err = reassembler.finish().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Payload 1 is missing fragment(s) 0, 2-4, 6-8, 10-12, 14-16, 18-20, 22-24, \
+             26-28, 30-32, 34-36 and 16 more range(s) of 100"
+        );
+    }
+
+    #[test]
+    fn test_forged_total_is_refused() {
+        let mut bytes = split(b"abc", 10, 1)[0].to_bytes();
+        bytes[HEADER_SIZE - 4..HEADER_SIZE].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(matches!(
+            Fragment::try_from(&bytes[..]),
+            Err(FragmentError::TooManyFragments(u32::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_reassemble_duplicate() {
+        let fragments = split(b"hello world", 4, 1);
+        let mut reassembler = Reassembler::new();
+        reassembler.push(fragments[1].clone()).unwrap();
+        assert!(matches!(
+            reassembler.push(fragments[1].clone()),
+            Err(FragmentError::Duplicate {
+                payload_id: 1,
+                index: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_reassemble_wrong_payload() {
+        let mut reassembler = Reassembler::new();
+        reassembler.push(split(b"abc", 1, 1).remove(0)).unwrap();
+        assert!(matches!(
+            reassembler.push(split(b"abc", 1, 2).remove(1)),
+            Err(FragmentError::WrongPayload {
+                expected: 1,
+                actual: 2
+            })
+        ));
+        assert!(matches!(
+            reassembler.push(split(b"abcd", 1, 1).remove(1)),
+            Err(FragmentError::TotalMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_split_empty_payload() {
+        let fragments = split(b"", 10, 3);
+        assert_eq!(fragments.len(), 1);
+
+        let mut reassembler = Reassembler::new();
+        reassembler.push(fragments[0].clone()).unwrap();
+        assert!(reassembler.finish().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_fragment() {
+        assert!(matches!(
+            Fragment::try_from(&b"PNGMFRAG\x01\x00"[..]),
+            Err(FragmentError::Truncated)
+        ));
+        let mut bytes = split(b"abc", 10, 1)[0].to_bytes();
+        bytes[MAGIC.len() + 1 + 4 + 3] = 5;
+        assert!(matches!(
+            Fragment::try_from(&bytes[..]),
+            Err(FragmentError::IndexOutOfRange { index: 5, total: 1 })
+        ));
+    }
+}
@@ -0,0 +1,412 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+
+/// Specialized `Result` type for IHDR operations.
+pub type Result<T> = std::result::Result<T, IhdrError>;
+
+/// The decoded contents of a PNG IHDR (image header) chunk.
+///
+/// IHDR is always 13 bytes long and laid out as:
+/// - 4 bytes: Width (big-endian u32)
+/// - 4 bytes: Height (big-endian u32)
+/// - 1 byte: Bit depth
+/// - 1 byte: Color type
+/// - 1 byte: Compression method (always 0)
+/// - 1 byte: Filter method (always 0)
+/// - 1 byte: Interlace method (0 = none, 1 = Adam7)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ihdr {
+    width: u32,
+    height: u32,
+    bit_depth: u8,
+    color_type: ColorType,
+    compression_method: u8,
+    filter_method: u8,
+    interlace_method: InterlaceMethod,
+}
+
+/// Pixel layouts defined by the PNG specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorType {
+    /// Each pixel is a grayscale sample
+    Grayscale = 0,
+    /// Each pixel is an R, G, B triple
+    Rgb = 2,
+    /// Each pixel is a palette index; a PLTE chunk must appear
+    Indexed = 3,
+    /// Each pixel is a grayscale sample followed by an alpha sample
+    GrayscaleAlpha = 4,
+    /// Each pixel is an R, G, B triple followed by an alpha sample
+    Rgba = 6,
+}
+
+/// Transmission order of the image data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterlaceMethod {
+    /// Scanlines are stored top to bottom
+    None = 0,
+    /// Pixels are stored in seven Adam7 passes
+    Adam7 = 1,
+}
+
+/// Errors that can occur when decoding an IHDR chunk.
+#[derive(Debug, thiserror::Error)]
+pub enum IhdrError {
+    /// Returned when the chunk passed in is not an IHDR chunk.
+    #[error("Expected IHDR chunk, got '{0}'")]
+    WrongChunkType(ChunkType),
+
+    /// Returned when the chunk data is not exactly 13 bytes long.
+    #[error("Invalid IHDR length: expected 13 bytes, got {0}")]
+    InvalidLength(usize),
+
+    /// Returned when the width or height is zero or exceeds 2³¹ - 1.
+    #[error("Invalid image dimensions {width}x{height}")]
+    InvalidDimensions { width: u32, height: u32 },
+
+    /// Returned when the color type byte is not one of 0, 2, 3, 4 or 6.
+    #[error("Invalid color type {0}")]
+    InvalidColorType(u8),
+
+    /// Returned when the bit depth is not allowed for the color type.
+    #[error("Bit depth {bit_depth} is not allowed for color type {color_type}")]
+    InvalidBitDepth {
+        bit_depth: u8,
+        color_type: ColorType,
+    },
+
+    /// Returned when the compression method is not 0 (deflate).
+    #[error("Unknown compression method {0}")]
+    InvalidCompressionMethod(u8),
+
+    /// Returned when the filter method is not 0 (adaptive filtering).
+    #[error("Unknown filter method {0}")]
+    InvalidFilterMethod(u8),
+
+    /// Returned when the interlace method is not 0 or 1.
+    #[error("Unknown interlace method {0}")]
+    InvalidInterlaceMethod(u8),
+}
+
+impl Ihdr {
+    /// Size of the IHDR data field in bytes.
+    pub const LENGTH: usize = 13;
+
+    /// Maximum width or height allowed by the PNG specification: 2³¹ - 1.
+    pub const MAX_DIMENSION: u32 = (1 << 31) - 1;
+
+    /// Creates a new header, validating every field against the PNG specification.
+    ///
+    /// Compression and filter methods are always 0, the only values the
+    /// specification defines.
+    pub fn new(
+        width: u32,
+        height: u32,
+        bit_depth: u8,
+        color_type: ColorType,
+        interlace_method: InterlaceMethod,
+    ) -> Result<Self> {
+        if width == 0 || height == 0 || width > Self::MAX_DIMENSION || height > Self::MAX_DIMENSION
+        {
+            return Err(IhdrError::InvalidDimensions { width, height });
+        }
+        if !color_type.allowed_bit_depths().contains(&bit_depth) {
+            return Err(IhdrError::InvalidBitDepth {
+                bit_depth,
+                color_type,
+            });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            bit_depth,
+            color_type,
+            compression_method: 0,
+            filter_method: 0,
+            interlace_method,
+        })
+    }
+
+    /// Image width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Image height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Number of bits per sample (or per palette index).
+    pub fn bit_depth(&self) -> u8 {
+        self.bit_depth
+    }
+
+    /// The pixel layout of the image.
+    pub fn color_type(&self) -> ColorType {
+        self.color_type
+    }
+
+    /// Compression method; 0 (deflate) is the only defined value.
+    pub fn compression_method(&self) -> u8 {
+        self.compression_method
+    }
+
+    /// Filter method; 0 (adaptive filtering) is the only defined value.
+    pub fn filter_method(&self) -> u8 {
+        self.filter_method
+    }
+
+    /// The order in which image data is transmitted.
+    pub fn interlace_method(&self) -> InterlaceMethod {
+        self.interlace_method
+    }
+
+    /// Returns the same header with a different interlace method.
+    pub fn with_interlace(mut self, interlace_method: InterlaceMethod) -> Self {
+        self.interlace_method = interlace_method;
+        self
+    }
+
+    /// Number of bits used by one pixel.
+    pub fn bits_per_pixel(&self) -> usize {
+        self.color_type.channels() * self.bit_depth as usize
+    }
+
+    /// Number of bytes used by one pixel, rounded up to at least 1.
+    ///
+    /// This is the distance used by the PNG filters to find the
+    /// corresponding byte of the previous pixel.
+    pub fn bytes_per_pixel(&self) -> usize {
+        self.bits_per_pixel().div_ceil(8)
+    }
+
+    /// Number of bytes in one unfiltered scanline of `width` pixels,
+    /// excluding the leading filter-type byte.
+    pub fn row_bytes(&self, width: u32) -> usize {
+        (width as usize * self.bits_per_pixel()).div_ceil(8)
+    }
+
+    /// Serializes the header into a new IHDR chunk.
+    pub fn to_chunk(&self) -> Chunk {
+        let mut data = Vec::with_capacity(Self::LENGTH);
+        data.extend_from_slice(&self.width.to_be_bytes());
+        data.extend_from_slice(&self.height.to_be_bytes());
+        data.push(self.bit_depth);
+        data.push(self.color_type as u8);
+        data.push(self.compression_method);
+        data.push(self.filter_method);
+        data.push(self.interlace_method as u8);
+        Chunk::new(
+            ChunkType::from_str("IHDR").expect("IHDR is a valid chunk type"),
+            data,
+        )
+    }
+}
+
+impl ColorType {
+    /// Number of samples that make up one pixel.
+    pub fn channels(&self) -> usize {
+        match self {
+            ColorType::Grayscale | ColorType::Indexed => 1,
+            ColorType::GrayscaleAlpha => 2,
+            ColorType::Rgb => 3,
+            ColorType::Rgba => 4,
+        }
+    }
+
+    /// Bit depths the PNG specification allows for this color type.
+    pub fn allowed_bit_depths(&self) -> &'static [u8] {
+        match self {
+            ColorType::Grayscale => &[1, 2, 4, 8, 16],
+            ColorType::Indexed => &[1, 2, 4, 8],
+            ColorType::Rgb | ColorType::GrayscaleAlpha | ColorType::Rgba => &[8, 16],
+        }
+    }
+}
+
+impl TryFrom<u8> for ColorType {
+    type Error = IhdrError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(ColorType::Grayscale),
+            2 => Ok(ColorType::Rgb),
+            3 => Ok(ColorType::Indexed),
+            4 => Ok(ColorType::GrayscaleAlpha),
+            6 => Ok(ColorType::Rgba),
+            _ => Err(IhdrError::InvalidColorType(value)),
+        }
+    }
+}
+
+impl TryFrom<u8> for InterlaceMethod {
+    type Error = IhdrError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(InterlaceMethod::None),
+            1 => Ok(InterlaceMethod::Adam7),
+            _ => Err(IhdrError::InvalidInterlaceMethod(value)),
+        }
+    }
+}
+
+/// Attempts to decode an IHDR chunk.
+///
+/// Checks the chunk type and length, then validates every field, including
+/// that the bit depth is legal for the color type.
+impl TryFrom<&Chunk> for Ihdr {
+    type Error = IhdrError;
+
+    fn try_from(chunk: &Chunk) -> Result<Self> {
+        if chunk.chunk_type().bytes() != *b"IHDR" {
+            return Err(IhdrError::WrongChunkType(*chunk.chunk_type()));
+        }
+
+        let data: &[u8; Self::LENGTH] = chunk
+            .data()
+            .try_into()
+            .map_err(|_| IhdrError::InvalidLength(chunk.data().len()))?;
+
+        let width = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        let height = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+        let bit_depth = data[8];
+        let color_type = ColorType::try_from(data[9])?;
+        if data[10] != 0 {
+            return Err(IhdrError::InvalidCompressionMethod(data[10]));
+        }
+        if data[11] != 0 {
+            return Err(IhdrError::InvalidFilterMethod(data[11]));
+        }
+        let interlace_method = InterlaceMethod::try_from(data[12])?;
+
+        Self::new(width, height, bit_depth, color_type, interlace_method)
+    }
+}
+
+impl fmt::Display for ColorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColorType::Grayscale => "Grayscale",
+            ColorType::Rgb => "RGB",
+            ColorType::Indexed => "Indexed",
+            ColorType::GrayscaleAlpha => "Grayscale+Alpha",
+            ColorType::Rgba => "RGBA",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl fmt::Display for InterlaceMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterlaceMethod::None => write!(f, "non-interlaced"),
+            InterlaceMethod::Adam7 => write!(f, "Adam7 interlaced"),
+        }
+    }
+}
+
+/// Formats the header as a one-line summary.
+///
+/// The output format is:
+/// ```text
+/// 50x50, 8-bit RGBA, compression 0, filter 0, non-interlaced
+/// ```
+impl fmt::Display for Ihdr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{}, {}-bit {}, compression {}, filter {}, {}",
+            self.width,
+            self.height,
+            self.bit_depth,
+            self.color_type,
+            self.compression_method,
+            self.filter_method,
+            self.interlace_method
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ihdr_chunk(data: &[u8]) -> Chunk {
+        Chunk::new(ChunkType::from_str("IHDR").unwrap(), data.to_vec())
+    }
+
+    #[test]
+    fn test_ihdr_from_chunk() {
+        let chunk = ihdr_chunk(&[0, 0, 0, 50, 0, 0, 0, 40, 8, 6, 0, 0, 0]);
+        let ihdr = Ihdr::try_from(&chunk).unwrap();
+        assert_eq!(ihdr.width(), 50);
+        assert_eq!(ihdr.height(), 40);
+        assert_eq!(ihdr.bit_depth(), 8);
+        assert_eq!(ihdr.color_type(), ColorType::Rgba);
+        assert_eq!(ihdr.interlace_method(), InterlaceMethod::None);
+        assert_eq!(ihdr.bytes_per_pixel(), 4);
+        assert_eq!(ihdr.row_bytes(ihdr.width()), 200);
+    }
+
+    #[test]
+    fn test_ihdr_round_trip() {
+        let ihdr = Ihdr::new(3, 7, 2, ColorType::Indexed, InterlaceMethod::Adam7).unwrap();
+        let parsed = Ihdr::try_from(&ihdr.to_chunk()).unwrap();
+        assert_eq!(ihdr, parsed);
+        assert_eq!(parsed.row_bytes(3), 1);
+    }
+
+    #[test]
+    fn test_ihdr_invalid_bit_depth() {
+        let chunk = ihdr_chunk(&[0, 0, 0, 1, 0, 0, 0, 1, 4, 2, 0, 0, 0]);
+        assert!(matches!(
+            Ihdr::try_from(&chunk),
+            Err(IhdrError::InvalidBitDepth { bit_depth: 4, .. })
+        ));
+
+        let chunk = ihdr_chunk(&[0, 0, 0, 1, 0, 0, 0, 1, 16, 3, 0, 0, 0]);
+        assert!(Ihdr::try_from(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_ihdr_invalid_fields() {
+        let chunk = ihdr_chunk(&[0, 0, 0, 0, 0, 0, 0, 1, 8, 0, 0, 0, 0]);
+        assert!(matches!(
+            Ihdr::try_from(&chunk),
+            Err(IhdrError::InvalidDimensions { .. })
+        ));
+
+        let chunk = ihdr_chunk(&[0, 0, 0, 1, 0, 0, 0, 1, 8, 1, 0, 0, 0]);
+        assert!(matches!(
+            Ihdr::try_from(&chunk),
+            Err(IhdrError::InvalidColorType(1))
+        ));
+
+        let chunk = ihdr_chunk(&[0, 0, 0, 1, 0, 0, 0, 1, 8, 0, 0, 0, 2]);
+        assert!(matches!(
+            Ihdr::try_from(&chunk),
+            Err(IhdrError::InvalidInterlaceMethod(2))
+        ));
+    }
+
+    #[test]
+    fn test_ihdr_wrong_chunk() {
+        let chunk = ihdr_chunk(&[0, 0, 0, 1]);
+        assert!(matches!(
+            Ihdr::try_from(&chunk),
+            Err(IhdrError::InvalidLength(4))
+        ));
+
+        let chunk = Chunk::new(ChunkType::from_str("IEND").unwrap(), vec![]);
+        assert!(matches!(
+            Ihdr::try_from(&chunk),
+            Err(IhdrError::WrongChunkType(_))
+        ));
+    }
+}
@@ -0,0 +1,654 @@
+use std::io::{ErrorKind, Read, Write};
+
+use flate2::Compression;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+
+use crate::chunk::Chunk;
+use crate::filter::{self, FilterStrategy, FilterType};
+use crate::ihdr::{ColorType, Ihdr, InterlaceMethod};
+
+/// Specialized `Result` type for image data operations.
+pub type Result<T> = std::result::Result<T, ImageDataError>;
+
+/// The decoded pixels of a PNG image, one unfiltered scanline per row.
+///
+/// Rows hold samples exactly as PNG packs them: big-endian for 16-bit
+/// images, and several pixels per byte for bit depths below 8, with each
+/// row padded to a whole byte. The filter-type bytes are removed.
+///
+/// # Examples
+///
+/// ```ignore
+/// let image = png.image_data()?;
+/// for row in image.rows() {
+///     println!("{:02x?}", &row[..4]);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageData {
+    header: Ihdr,
+    /// Unfiltered scanlines, back to back
+    pixels: Vec<u8>,
+}
+
+/// Settings for compressing pixels back into IDAT chunks.
+///
+/// The defaults are adaptive filtering, zlib level 6 and IDAT chunks of at
+/// most 8 KiB, the same choices libpng makes.
+///
+/// # Examples
+///
+/// ```ignore
+/// let options = ImageEncodeOptions::default()
+///     .with_filter(FilterStrategy::Fixed(FilterType::Paeth))
+///     .with_level(9);
+/// png.set_image_data(&image, &options)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageEncodeOptions {
+    filter: FilterStrategy,
+    level: u32,
+    max_chunk_size: usize,
+    interlace: Option<InterlaceMethod>,
+}
+
+impl ImageEncodeOptions {
+    /// Default size limit for each IDAT chunk.
+    pub const DEFAULT_MAX_CHUNK_SIZE: usize = 8192;
+
+    /// Sets how each scanline's filter is chosen.
+    pub fn with_filter(mut self, filter: FilterStrategy) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Sets the zlib compression level, from 0 (store only) to 9 (smallest).
+    ///
+    /// Levels above 9 are treated as 9.
+    pub fn with_level(mut self, level: u32) -> Self {
+        self.level = level.min(9);
+        self
+    }
+
+    /// Sets the largest data size of each IDAT chunk.
+    ///
+    /// The size is kept between 1 byte and the PNG limit of 2³¹ - 1.
+    pub fn with_max_chunk_size(mut self, size: usize) -> Self {
+        self.max_chunk_size = size.clamp(1, Chunk::MAX_DATA_SIZE);
+        self
+    }
+
+    /// Stores the image with this interlace method instead of the image's own.
+    ///
+    /// This converts between interlaced and non-interlaced files on save.
+    pub fn with_interlace(mut self, interlace: InterlaceMethod) -> Self {
+        self.interlace = Some(interlace);
+        self
+    }
+
+    /// How each scanline's filter is chosen.
+    pub fn filter(&self) -> FilterStrategy {
+        self.filter
+    }
+
+    /// The zlib compression level.
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// The largest data size of each IDAT chunk.
+    pub fn max_chunk_size(&self) -> usize {
+        self.max_chunk_size
+    }
+
+    /// The interlace method to store images with, or `None` to keep each image's own.
+    pub fn interlace(&self) -> Option<InterlaceMethod> {
+        self.interlace
+    }
+
+    /// The header an image with `header` is stored under: the same, apart from
+    /// the interlace method if one was chosen.
+    pub fn header_for(&self, header: &Ihdr) -> Ihdr {
+        match self.interlace {
+            Some(interlace) => header.with_interlace(interlace),
+            None => *header,
+        }
+    }
+}
+
+impl Default for ImageEncodeOptions {
+    fn default() -> Self {
+        Self {
+            filter: FilterStrategy::Adaptive,
+            level: 6,
+            max_chunk_size: Self::DEFAULT_MAX_CHUNK_SIZE,
+            interlace: None,
+        }
+    }
+}
+
+/// Errors that can occur when decoding image data.
+#[derive(Debug, thiserror::Error)]
+pub enum ImageDataError {
+    /// Returned when the PNG has no IDAT chunks.
+    #[error("No IDAT chunks found")]
+    NoImageData,
+
+    /// Returned when the zlib stream is corrupt.
+    #[error("Image data could not be decompressed: {0}")]
+    Decompression(#[source] std::io::Error),
+
+    /// Returned when the decompressed data ends before the last scanline.
+    #[error("Image data is truncated: expected {expected} bytes, got {actual}")]
+    Truncated { expected: usize, actual: usize },
+
+    /// Returned when a scanline starts with a filter type other than 0-4.
+    ///
+    /// Scanlines are counted from the start of the stream, across all Adam7 passes.
+    #[error("Invalid filter type {filter} on scanline {row}")]
+    InvalidFilter { row: usize, filter: u8 },
+
+    /// Returned when the image is too large to hold in memory.
+    #[error("Image of {width}x{height} pixels is too large to decode")]
+    TooLarge { width: u32, height: u32 },
+
+    /// Returned when pixel data does not match the size the header requires.
+    #[error("Pixel data has {actual} bytes, but the header requires {expected}")]
+    InvalidLength { expected: usize, actual: usize },
+}
+
+impl ImageData {
+    /// Wraps raw pixel rows laid out as described by `header`.
+    ///
+    /// `pixels` holds `header.height()` rows of `header.row_bytes(width)` bytes
+    /// each, back to back, without filter-type bytes. Rows are always in
+    /// display order, even when the header says the image is interlaced.
+    pub fn new(header: Ihdr, pixels: Vec<u8>) -> Result<Self> {
+        let expected = header
+            .row_bytes(header.width())
+            .checked_mul(header.height() as usize)
+            .ok_or(ImageDataError::TooLarge {
+                width: header.width(),
+                height: header.height(),
+            })?;
+        if pixels.len() != expected {
+            return Err(ImageDataError::InvalidLength {
+                expected,
+                actual: pixels.len(),
+            });
+        }
+        Ok(Self { header, pixels })
+    }
+
+    /// Inflates and unfilters the concatenated contents of the IDAT chunks.
+    ///
+    /// `header` supplies the geometry. Adam7 interlaced images are
+    /// de-interlaced, so the rows come out in display order either way.
+    /// Data after the last scanline is ignored, as most decoders do.
+    pub fn decode(header: &Ihdr, compressed: &[u8]) -> Result<Self> {
+        let passes = passes(header);
+        let too_large = || ImageDataError::TooLarge {
+            width: header.width(),
+            height: header.height(),
+        };
+        let expected = passes
+            .iter()
+            .try_fold(0usize, |total, pass| {
+                total.checked_add(pass.filtered_len(header)?)
+            })
+            .ok_or_else(too_large)?;
+
+        // Never inflate more than the image needs, however long the stream claims to be
+        let mut filtered = Vec::new();
+        let result = ZlibDecoder::new(compressed)
+            .take(expected as u64)
+            .read_to_end(&mut filtered);
+        match result {
+            // A stream cut off mid-block is truncation, not corruption
+            Err(e) if e.kind() != ErrorKind::UnexpectedEof => {
+                return Err(ImageDataError::Decompression(e));
+            }
+            _ => {}
+        }
+        if filtered.len() < expected {
+            return Err(ImageDataError::Truncated {
+                expected,
+                actual: filtered.len(),
+            });
+        }
+
+        let mut rest = &filtered[..];
+        let mut first_row = 0;
+        let mut pass_pixels = Vec::with_capacity(passes.len());
+        for pass in &passes {
+            let (data, tail) = rest.split_at(pass.filtered_len(header).expect("checked above"));
+            pass_pixels.push(unfilter_pass(header, pass, data, first_row)?);
+            first_row += pass.height;
+            rest = tail;
+        }
+
+        let pixels = match header.interlace_method() {
+            InterlaceMethod::None => pass_pixels.pop().expect("one pass"),
+            InterlaceMethod::Adam7 => {
+                let row_bytes = header.row_bytes(header.width());
+                let mut pixels = vec![0; row_bytes * header.height() as usize];
+                for (pass, data) in passes.iter().zip(&pass_pixels) {
+                    pass.scatter(header, data, &mut pixels);
+                }
+                pixels
+            }
+        };
+
+        Ok(Self {
+            header: *header,
+            pixels,
+        })
+    }
+
+    /// The header describing the image geometry and pixel format.
+    pub fn header(&self) -> &Ihdr {
+        &self.header
+    }
+
+    /// Number of bytes in each row.
+    pub fn row_bytes(&self) -> usize {
+        self.header.row_bytes(self.header.width())
+    }
+
+    /// Iterates over the rows, top to bottom.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[u8]> {
+        // chunks_exact panics on 0; rows are never empty for a valid header
+        self.pixels.chunks_exact(self.row_bytes().max(1))
+    }
+
+    /// Returns row `y`, or `None` if it is past the bottom of the image.
+    pub fn row(&self, y: usize) -> Option<&[u8]> {
+        self.rows().nth(y)
+    }
+
+    /// All rows, back to back.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// All rows, back to back, for editing pixels in place.
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+
+    /// Consumes the image and returns its rows, back to back.
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    /// Filters every scanline and deflates the result into a zlib stream.
+    ///
+    /// This is the inverse of [`ImageData::decode`]; the stream can be split
+    /// across IDAT chunks of any size. The image is interlaced as
+    /// `options.header_for(self.header())` says.
+    pub fn encode(&self, options: &ImageEncodeOptions) -> Vec<u8> {
+        let header = options.header_for(&self.header);
+        // Filtering rarely helps palette or sub-byte images, so the spec recommends None
+        let strategy = match options.filter() {
+            FilterStrategy::Adaptive
+                if header.color_type() == ColorType::Indexed || header.bit_depth() < 8 =>
+            {
+                FilterStrategy::Fixed(FilterType::None)
+            }
+            strategy => strategy,
+        };
+
+        let mut filtered = Vec::new();
+        for pass in passes(&header) {
+            match header.interlace_method() {
+                InterlaceMethod::None => {
+                    filter_pass(&header, &pass, &self.pixels, strategy, &mut filtered)
+                }
+                InterlaceMethod::Adam7 => {
+                    let pixels = pass.gather(&header, &self.pixels);
+                    filter_pass(&header, &pass, &pixels, strategy, &mut filtered);
+                }
+            }
+        }
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(options.level()));
+        encoder
+            .write_all(&filtered)
+            .expect("writing to a Vec cannot fail");
+        encoder.finish().expect("writing to a Vec cannot fail")
+    }
+}
+
+/// Adam7 passes as (x start, y start, x step, y step).
+const ADAM7: [(usize, usize, usize, usize); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
+
+/// One sub-image of the stored data: the whole image, or one Adam7 pass.
+struct Pass {
+    x_start: usize,
+    y_start: usize,
+    x_step: usize,
+    y_step: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Pass {
+    /// Bytes the pass occupies in the decompressed stream, including filter bytes.
+    ///
+    /// Empty passes store nothing at all, not even filter bytes.
+    fn filtered_len(&self, header: &Ihdr) -> Option<usize> {
+        if self.width == 0 {
+            return Some(0);
+        }
+        (header.row_bytes(self.width as u32) + 1).checked_mul(self.height)
+    }
+
+    /// Copies the pass's pixels into their places in the full image.
+    fn scatter(&self, header: &Ihdr, pass: &[u8], image: &mut [u8]) {
+        let pass_row_bytes = header.row_bytes(self.width as u32);
+        let row_bytes = header.row_bytes(header.width());
+        for y in 0..self.height {
+            let source = &pass[y * pass_row_bytes..][..pass_row_bytes];
+            let target_y = self.y_start + y * self.y_step;
+            let target = &mut image[target_y * row_bytes..][..row_bytes];
+            for x in 0..self.width {
+                let target_x = self.x_start + x * self.x_step;
+                copy_pixel(source, x, target, target_x, header.bits_per_pixel());
+            }
+        }
+    }
+
+    /// Collects the pass's pixels from the full image into rows of their own.
+    fn gather(&self, header: &Ihdr, image: &[u8]) -> Vec<u8> {
+        let pass_row_bytes = header.row_bytes(self.width as u32);
+        let row_bytes = header.row_bytes(header.width());
+        let mut pass = vec![0; pass_row_bytes * self.height];
+        for y in 0..self.height {
+            let source_y = self.y_start + y * self.y_step;
+            let source = &image[source_y * row_bytes..][..row_bytes];
+            let target = &mut pass[y * pass_row_bytes..][..pass_row_bytes];
+            for x in 0..self.width {
+                let source_x = self.x_start + x * self.x_step;
+                copy_pixel(source, source_x, target, x, header.bits_per_pixel());
+            }
+        }
+        pass
+    }
+}
+
+/// The sub-images stored for `header`: one for the whole image, or the seven Adam7 passes.
+fn passes(header: &Ihdr) -> Vec<Pass> {
+    let width = header.width() as usize;
+    let height = header.height() as usize;
+    match header.interlace_method() {
+        InterlaceMethod::None => vec![Pass {
+            x_start: 0,
+            y_start: 0,
+            x_step: 1,
+            y_step: 1,
+            width,
+            height,
+        }],
+        InterlaceMethod::Adam7 => ADAM7
+            .iter()
+            .map(|&(x_start, y_start, x_step, y_step)| {
+                let span = |size: usize, start: usize, step: usize| {
+                    size.saturating_sub(start).div_ceil(step)
+                };
+                let (width, height) = (span(width, x_start, x_step), span(height, y_start, y_step));
+                Pass {
+                    x_start,
+                    y_start,
+                    x_step,
+                    y_step,
+                    // A pass with no columns has no rows either
+                    width: if height == 0 { 0 } else { width },
+                    height: if width == 0 { 0 } else { height },
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Reverses the filters of one pass.
+///
+/// `first_row` numbers the pass's first scanline within the whole stream, for errors.
+fn unfilter_pass(header: &Ihdr, pass: &Pass, filtered: &[u8], first_row: usize) -> Result<Vec<u8>> {
+    let row_bytes = header.row_bytes(pass.width as u32);
+    let bytes_per_pixel = header.bytes_per_pixel();
+    let mut pixels = Vec::with_capacity(row_bytes * pass.height);
+    for (y, line) in filtered.chunks_exact(row_bytes + 1).enumerate() {
+        let filter =
+            FilterType::try_from(line[0]).map_err(|filter| ImageDataError::InvalidFilter {
+                row: first_row + y,
+                filter,
+            })?;
+        let start = pixels.len();
+        pixels.extend_from_slice(&line[1..]);
+        let (done, current) = pixels.split_at_mut(start);
+        let previous = (y > 0).then(|| &done[start - row_bytes..]);
+        filter::unfilter(filter, current, previous, bytes_per_pixel);
+    }
+    Ok(pixels)
+}
+
+/// Filters the rows of one pass and appends them, each with its filter-type byte.
+fn filter_pass(
+    header: &Ihdr,
+    pass: &Pass,
+    pixels: &[u8],
+    strategy: FilterStrategy,
+    filtered: &mut Vec<u8>,
+) {
+    if pass.width == 0 {
+        return;
+    }
+    let row_bytes = header.row_bytes(pass.width as u32);
+    let bytes_per_pixel = header.bytes_per_pixel();
+    let mut previous = None;
+    for row in pixels.chunks_exact(row_bytes) {
+        let start = filtered.len() + 1;
+        filtered.resize(start + row_bytes, 0);
+        let out = &mut filtered[start..];
+        let filter_type = match strategy {
+            FilterStrategy::Fixed(filter_type) => {
+                filter::filter(filter_type, row, previous, bytes_per_pixel, out);
+                filter_type
+            }
+            FilterStrategy::Adaptive => filter::choose_filter(row, previous, bytes_per_pixel, out),
+        };
+        filtered[start - 1] = filter_type as u8;
+        previous = Some(row);
+    }
+}
+
+/// Copies pixel `source_x` of one row to pixel `target_x` of another.
+///
+/// Pixels narrower than a byte are packed most significant bits first.
+fn copy_pixel(source: &[u8], source_x: usize, target: &mut [u8], target_x: usize, bits: usize) {
+    if bits >= 8 {
+        let size = bits / 8;
+        target[target_x * size..][..size].copy_from_slice(&source[source_x * size..][..size]);
+        return;
+    }
+    let per_byte = 8 / bits;
+    let mask = (1u8 << bits) - 1;
+    let shift = |x: usize| 8 - bits - (x % per_byte) * bits;
+    let value = (source[source_x / per_byte] >> shift(source_x)) & mask;
+    let byte = &mut target[target_x / per_byte];
+    *byte = (*byte & !(mask << shift(target_x))) | (value << shift(target_x));
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::ihdr::ColorType;
+    use crate::text::compress;
+
+    fn header(width: u32, height: u32, bit_depth: u8, color_type: ColorType) -> Ihdr {
+        Ihdr::new(width, height, bit_depth, color_type, InterlaceMethod::None).unwrap()
+    }
+
+    /// Varied, repeatable pixel bytes filling an image with this header.
+    pub(crate) fn sample_pixels(header: &Ihdr) -> Vec<u8> {
+        let size = header.row_bytes(header.width()) * header.height() as usize;
+        (0..size).map(|i| (i * 37 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_decode_all_filter_types() {
+        // 2x5 RGB; every row stores [10, 20, 30, 40, 50, 60] under a different filter
+        let ihdr = header(2, 5, 8, ColorType::Rgb);
+        let filtered = [
+            vec![0, 10, 20, 30, 40, 50, 60],
+            vec![1, 10, 20, 30, 30, 30, 30],
+            vec![2, 0, 0, 0, 0, 0, 0],
+            vec![3, 5, 10, 15, 15, 15, 15],
+            vec![4, 0, 0, 0, 0, 0, 0],
+        ]
+        .concat();
+
+        let image = ImageData::decode(&ihdr, &compress(&filtered)).unwrap();
+        assert_eq!(image.rows().len(), 5);
+        for row in image.rows() {
+            assert_eq!(row, [10, 20, 30, 40, 50, 60]);
+        }
+    }
+
+    #[test]
+    fn test_decode_packed_rows() {
+        // 10 one-bit pixels fit in 2 bytes per row
+        let ihdr = header(10, 2, 1, ColorType::Grayscale);
+        let filtered = [0, 0b1010_1010, 0b1100_0000, 0, 0xff, 0xc0];
+        let image = ImageData::decode(&ihdr, &compress(&filtered)).unwrap();
+        assert_eq!(image.row_bytes(), 2);
+        assert_eq!(image.row(1), Some(&[0xff, 0xc0][..]));
+        assert_eq!(image.row(2), None);
+    }
+
+    #[test]
+    fn test_truncated_stream() {
+        let ihdr = header(2, 2, 8, ColorType::Grayscale);
+        let error = ImageData::decode(&ihdr, &compress(&[0, 1, 2, 0])).unwrap_err();
+        assert!(matches!(
+            error,
+            ImageDataError::Truncated {
+                expected: 6,
+                actual: 4
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cut_off_zlib_stream() {
+        let ihdr = header(2, 2, 8, ColorType::Grayscale);
+        let compressed = compress(&[0, 1, 2, 0, 3, 4]);
+        let error = ImageData::decode(&ihdr, &compressed[..compressed.len() / 2]).unwrap_err();
+        assert!(matches!(
+            error,
+            ImageDataError::Truncated { expected: 6, .. }
+        ));
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let ihdr = header(7, 5, 8, ColorType::Rgba);
+        let image = ImageData::new(ihdr, sample_pixels(&ihdr)).unwrap();
+
+        for filter in FilterType::ALL.map(FilterStrategy::Fixed) {
+            let options = ImageEncodeOptions::default().with_filter(filter);
+            let compressed = image.encode(&options);
+            assert_eq!(ImageData::decode(&ihdr, &compressed).unwrap(), image);
+        }
+        let compressed = image.encode(&ImageEncodeOptions::default().with_level(0));
+        assert_eq!(ImageData::decode(&ihdr, &compressed).unwrap(), image);
+    }
+
+    #[test]
+    fn test_decode_adam7_layout() {
+        // A 2x2 image stores pass 1 (0,0), pass 6 (1,0) and pass 7 (the second row)
+        let ihdr = header(2, 2, 8, ColorType::Grayscale).with_interlace(InterlaceMethod::Adam7);
+        let filtered = [0, 1, 0, 2, 0, 3, 4];
+        let image = ImageData::decode(&ihdr, &compress(&filtered)).unwrap();
+        assert_eq!(image.pixels(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_adam7_round_trip() {
+        let interlaced = ImageEncodeOptions::default().with_interlace(InterlaceMethod::Adam7);
+        for (bit_depth, color_type) in [
+            (8, ColorType::Rgb),
+            (16, ColorType::Grayscale),
+            (1, ColorType::Grayscale),
+            (4, ColorType::Indexed),
+        ] {
+            let ihdr = header(13, 11, bit_depth, color_type);
+            let mut pixels = sample_pixels(&ihdr);
+            // Padding bits at the end of each row belong to no pixel, so no pass stores them
+            let used_bits = 13 * ihdr.bits_per_pixel() % 8;
+            if used_bits > 0 {
+                for row in pixels.chunks_exact_mut(ihdr.row_bytes(13)) {
+                    *row.last_mut().unwrap() &= 0xff << (8 - used_bits);
+                }
+            }
+            let image = ImageData::new(ihdr, pixels).unwrap();
+
+            let compressed = image.encode(&interlaced);
+            let adam7 = interlaced.header_for(&ihdr);
+            let decoded = ImageData::decode(&adam7, &compressed).unwrap();
+            assert_eq!(
+                decoded.pixels(),
+                image.pixels(),
+                "{bit_depth}-bit {color_type}"
+            );
+            assert_eq!(decoded.header().interlace_method(), InterlaceMethod::Adam7);
+
+            // And back to a plain image
+            let plain = ImageEncodeOptions::default().with_interlace(InterlaceMethod::None);
+            let decoded = ImageData::decode(&ihdr, &decoded.encode(&plain)).unwrap();
+            assert_eq!(decoded.pixels(), image.pixels());
+        }
+    }
+
+    #[test]
+    fn test_adaptive_uses_none_for_packed_pixels() {
+        let ihdr = header(16, 2, 1, ColorType::Grayscale);
+        let image = ImageData::new(ihdr, vec![0x0f, 0xf0, 0x0f, 0xf0]).unwrap();
+        let mut filtered = Vec::new();
+        ZlibDecoder::new(&image.encode(&ImageEncodeOptions::default())[..])
+            .read_to_end(&mut filtered)
+            .unwrap();
+        assert_eq!(filtered, [0, 0x0f, 0xf0, 0, 0x0f, 0xf0]);
+    }
+
+    #[test]
+    fn test_new_checks_length() {
+        let ihdr = header(2, 2, 8, ColorType::Rgb);
+        assert!(matches!(
+            ImageData::new(ihdr, vec![0; 11]),
+            Err(ImageDataError::InvalidLength {
+                expected: 12,
+                actual: 11
+            })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_filter_type() {
+        let ihdr = header(2, 2, 8, ColorType::Grayscale);
+        let error = ImageData::decode(&ihdr, &compress(&[0, 1, 2, 7, 3, 4])).unwrap_err();
+        assert!(matches!(
+            error,
+            ImageDataError::InvalidFilter { row: 1, filter: 7 }
+        ));
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use pngme::{Chunk, ChunkType, ChunkTypeError};
+
+use crate::report::breakdown;
+
+/// Bytes shown per hexdump line.
+const BYTES_PER_LINE: usize = 16;
+
+/// Picks a chunk for `inspect`: by index in the chunk list, or the first of a type.
+#[derive(Clone, Copy)]
+pub enum ChunkSelector {
+    Index(usize),
+    Type(ChunkType),
+}
+
+impl FromStr for ChunkSelector {
+    type Err = ChunkTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(index) => Ok(ChunkSelector::Index(index)),
+            Err(_) => ChunkType::from_str(s).map(ChunkSelector::Type),
+        }
+    }
+}
+
+/// A detailed, multi-section description of one chunk.
+///
+/// The output looks like:
+/// ```text
+/// Chunk 5: RuSt at byte 4776
+///   Length: 3 data bytes
+///   CRC: 0x9EB0F5A0 (valid)
+///
+/// Properties:
+///   R  critical   decoders must understand this chunk
+///   ...
+///
+/// Fields:
+///   ...
+///
+/// Data (starting at byte 4784):
+/// 000012b0  68 65 79                                          |hey|
+/// ```
+pub struct Inspection<'a> {
+    pub index: usize,
+    /// Byte offset of the chunk in the file
+    pub offset: usize,
+    pub chunk: &'a Chunk,
+    /// Maximum number of data bytes to dump; `None` dumps everything
+    pub limit: Option<usize>,
+}
+
+impl fmt::Display for Inspection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chunk = self.chunk;
+        let chunk_type = chunk.chunk_type();
+        writeln!(
+            f,
+            "Chunk {}: {} at byte {}",
+            self.index, chunk_type, self.offset
+        )?;
+        writeln!(f, "  Length: {} data bytes", chunk.length())?;
+        if chunk.has_valid_crc() {
+            writeln!(f, "  CRC: 0x{:08X} (valid)", chunk.crc())?;
+        } else {
+            let expected = Chunk::calculate_crc(chunk_type, chunk.data());
+            writeln!(
+                f,
+                "  CRC: 0x{:08X} (MISMATCH, computed 0x{expected:08X})",
+                chunk.crc()
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Properties:")?;
+        let letters: Vec<char> = chunk_type.bytes().iter().map(|&b| b as char).collect();
+        let properties = [
+            (
+                chunk_type.is_critical(),
+                ("critical", "decoders must understand this chunk"),
+                ("ancillary", "decoders may ignore this chunk"),
+            ),
+            (
+                chunk_type.is_public(),
+                ("public", "defined by the PNG specification or registered"),
+                ("private", "application-specific, not registered"),
+            ),
+            (
+                chunk_type.is_reserved_bit_valid(),
+                ("reserved", "reserved bit is clear, as required"),
+                (
+                    "INVALID",
+                    "reserved bit is set; conforming decoders reject this",
+                ),
+            ),
+            (
+                chunk_type.is_safe_to_copy(),
+                ("safe", "editors may copy this chunk unchanged"),
+                (
+                    "unsafe",
+                    "editors must drop this chunk if they change critical chunks",
+                ),
+            ),
+        ];
+        for (letter, (set, yes, no)) in letters.iter().zip(properties) {
+            let (name, meaning) = if set { yes } else { no };
+            writeln!(f, "  {letter}  {name:<10} {meaning}")?;
+        }
+        for warning in chunk_type.warnings() {
+            writeln!(f, "  Warning: {warning}")?;
+        }
+
+        let fields = breakdown(chunk).map_or_else(Vec::new, |breakdown| breakdown.fields);
+        if !fields.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Fields:")?;
+            let width = fields.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+            for (name, value) in fields {
+                writeln!(f, "  {name:<width$}  {value}")?;
+            }
+        }
+
+        let data_offset = self.offset + Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE;
+        let data = chunk.data();
+        let shown = self.limit.map_or(data.len(), |limit| limit.min(data.len()));
+        writeln!(f)?;
+        writeln!(f, "Data (starting at byte {data_offset}):")?;
+        write!(f, "{}", hexdump(&data[..shown], data_offset))?;
+        if shown < data.len() {
+            writeln!(f, "... {} more bytes", data.len() - shown)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats bytes as offset, hex and ASCII columns, one line per 16 bytes.
+///
+/// Offsets start at `base` so they match positions in the file.
+pub fn hexdump(data: &[u8], base: usize) -> String {
+    let mut out = String::new();
+    for (line, bytes) in data.chunks(BYTES_PER_LINE).enumerate() {
+        let _ = write!(out, "{:08x} ", base + line * BYTES_PER_LINE);
+        for column in 0..BYTES_PER_LINE {
+            if column == BYTES_PER_LINE / 2 {
+                out.push(' ');
+            }
+            match bytes.get(column) {
+                Some(byte) => {
+                    let _ = write!(out, " {byte:02x}");
+                }
+                None => out.push_str("   "),
+            }
+        }
+        let ascii: String = bytes
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let _ = writeln!(out, "  |{ascii}|");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hexdump_layout() {
+        let data: Vec<u8> = (0x41..0x41 + 18).collect();
+        assert_eq!(
+            hexdump(&data, 0x20),
+            "00000020  41 42 43 44 45 46 47 48  49 4a 4b 4c 4d 4e 4f 50  |ABCDEFGHIJKLMNOP|\n\
+             00000030  51 52                                             |QR|\n"
+        );
+        assert_eq!(
+            hexdump(&[0, b' ', 0x7f], 0),
+            format!("00000000  00 20 7f{}  |. .|\n", " ".repeat(40))
+        );
+    }
+
+    #[test]
+    fn test_fields_section() {
+        let chunk = Chunk::new(
+            ChunkType::from_str("tEXt").unwrap(),
+            b"Comment\0hello".to_vec(),
+        );
+        let inspection = Inspection {
+            index: 1,
+            offset: 33,
+            chunk: &chunk,
+            limit: None,
+        }
+        .to_string();
+        assert!(inspection.contains("Fields:\n  Keyword  Comment\n  Text     hello\n"));
+        assert!(inspection.contains("Data (starting at byte 41):\n"));
+    }
+}
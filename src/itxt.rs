@@ -0,0 +1,265 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::text::{self, Result, TextError};
+
+/// A decoded PNG iTXt chunk: international text in UTF-8.
+///
+/// On the wire the chunk data is laid out as:
+/// - 1-79 bytes: Keyword (Latin-1)
+/// - 1 byte: Null separator
+/// - 1 byte: Compression flag (0 = uncompressed, 1 = compressed)
+/// - 1 byte: Compression method (always 0, zlib deflate)
+/// - 0+ bytes: Language tag (ASCII, RFC 3066, e.g. "en" or "zh-TW")
+/// - 1 byte: Null separator
+/// - 0+ bytes: Translated keyword (UTF-8)
+/// - 1 byte: Null separator
+/// - N bytes: Text (UTF-8, optionally zlib compressed)
+///
+/// # Examples
+///
+/// ```ignore
+/// let itxt = ItxtChunk::new("Title", "Sonnenaufgang")?
+///     .with_language("de", "Titel")?
+///     .with_compression(true);
+/// png.insert_before_iend(itxt.to_chunk())?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItxtChunk {
+    keyword: String,
+    compressed: bool,
+    language_tag: String,
+    translated_keyword: String,
+    text: String,
+}
+
+impl ItxtChunk {
+    /// The only compression method defined by the PNG specification (zlib deflate).
+    pub const COMPRESSION_METHOD: u8 = 0;
+
+    /// Creates a new uncompressed iTXt entry with no language information.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ItxtChunk)` - If the keyword is valid
+    /// * `Err(TextError)` - If the keyword fails validation or the text contains a null
+    pub fn new(keyword: &str, text: &str) -> Result<Self> {
+        text::validate_keyword(keyword)?;
+        text::validate_no_null(text)?;
+
+        Ok(Self {
+            keyword: keyword.to_string(),
+            compressed: false,
+            language_tag: String::new(),
+            translated_keyword: String::new(),
+            text: text.to_string(),
+        })
+    }
+
+    /// Sets the language of the text and the keyword translated into that language.
+    ///
+    /// An empty language tag means the language is unknown.
+    pub fn with_language(mut self, language_tag: &str, translated_keyword: &str) -> Result<Self> {
+        validate_language_tag(language_tag)?;
+        text::validate_no_null(translated_keyword)?;
+        self.language_tag = language_tag.to_string();
+        self.translated_keyword = translated_keyword.to_string();
+        Ok(self)
+    }
+
+    /// Sets whether the text is deflate-compressed when serialized.
+    pub fn with_compression(mut self, compressed: bool) -> Self {
+        self.compressed = compressed;
+        self
+    }
+
+    /// The keyword identifying this entry, such as "Title" or "Comment".
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    /// True if the text is stored compressed.
+    pub fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// The RFC 3066 language tag of the text; empty if unknown.
+    pub fn language_tag(&self) -> &str {
+        &self.language_tag
+    }
+
+    /// The keyword translated into the text's language; may be empty.
+    pub fn translated_keyword(&self) -> &str {
+        &self.translated_keyword
+    }
+
+    /// The (uncompressed) UTF-8 text stored under the keyword.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Serializes the entry into a new iTXt chunk, compressing the text if requested.
+    pub fn to_chunk(&self) -> Chunk {
+        let mut data = text::encode_latin1(&self.keyword).expect("keyword validated as Latin-1");
+        data.push(0);
+        data.push(self.compressed as u8);
+        data.push(Self::COMPRESSION_METHOD);
+        data.extend_from_slice(self.language_tag.as_bytes());
+        data.push(0);
+        data.extend_from_slice(self.translated_keyword.as_bytes());
+        data.push(0);
+        if self.compressed {
+            data.extend(text::compress(self.text.as_bytes()));
+        } else {
+            data.extend_from_slice(self.text.as_bytes());
+        }
+        Chunk::new(
+            ChunkType::from_str("iTXt").expect("iTXt is a valid chunk type"),
+            data,
+        )
+    }
+}
+
+/// Attempts to decode an iTXt chunk, inflating its text if compressed.
+impl TryFrom<&Chunk> for ItxtChunk {
+    type Error = TextError;
+
+    fn try_from(chunk: &Chunk) -> Result<Self> {
+        text::expect_chunk_type(chunk, "iTXt")?;
+        let (keyword, rest) = text::split_keyword(chunk.data())?;
+
+        let [flag, method, rest @ ..] = rest else {
+            return Err(TextError::Truncated);
+        };
+        let compressed = match flag {
+            0 => false,
+            1 => true,
+            _ => return Err(TextError::InvalidCompressionFlag(*flag)),
+        };
+        if compressed && *method != Self::COMPRESSION_METHOD {
+            return Err(TextError::UnknownCompressionMethod(*method));
+        }
+
+        let (language_tag, rest) = split_null(rest)?;
+        let language_tag = String::from_utf8(language_tag.to_vec())?;
+        validate_language_tag(&language_tag)?;
+
+        let (translated_keyword, text) = split_null(rest)?;
+        let translated_keyword = String::from_utf8(translated_keyword.to_vec())?;
+
+        let text = if compressed {
+            String::from_utf8(text::decompress(text)?)?
+        } else {
+            String::from_utf8(text.to_vec())?
+        };
+
+        Ok(Self {
+            keyword,
+            compressed,
+            language_tag,
+            translated_keyword,
+            text,
+        })
+    }
+}
+
+impl fmt::Display for ItxtChunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.keyword)?;
+        if !self.language_tag.is_empty() {
+            write!(f, " [{}]", self.language_tag)?;
+        }
+        write!(f, ": {}", self.text)
+    }
+}
+
+/// Checks a language tag against RFC 3066: hyphen-separated groups of
+/// 1-8 ASCII letters or digits. The empty tag (unknown language) is allowed.
+pub fn validate_language_tag(tag: &str) -> Result<()> {
+    if tag.is_empty() {
+        return Ok(());
+    }
+    let valid = tag.split('-').all(|part| {
+        (1..=8).contains(&part.len()) && part.bytes().all(|b| b.is_ascii_alphanumeric())
+    });
+    if !valid {
+        return Err(TextError::InvalidLanguageTag(tag.to_string()));
+    }
+    Ok(())
+}
+
+/// Splits data at the first null byte, failing if there is none.
+fn split_null(data: &[u8]) -> Result<(&[u8], &[u8])> {
+    let separator = data
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or(TextError::MissingSeparator)?;
+    Ok((&data[..separator], &data[separator + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_itxt_chunk_round_trip() {
+        let itxt = ItxtChunk::new("Title", "\u{65e5}\u{51fa}")
+            .unwrap()
+            .with_language("ja", "\u{984c}\u{540d}")
+            .unwrap();
+        let chunk = itxt.to_chunk();
+        assert_eq!(&chunk.chunk_type().to_string(), "iTXt");
+        assert!(chunk.data().starts_with(b"Title\0\0\0ja\0"));
+
+        let parsed = ItxtChunk::try_from(&chunk).unwrap();
+        assert_eq!(parsed, itxt);
+        assert_eq!(parsed.language_tag(), "ja");
+        assert_eq!(parsed.translated_keyword(), "\u{984c}\u{540d}");
+        assert_eq!(parsed.text(), "\u{65e5}\u{51fa}");
+    }
+
+    #[test]
+    fn test_itxt_compressed_round_trip() {
+        let message = "Snowman \u{2603} ".repeat(100);
+        let itxt = ItxtChunk::new("Comment", &message)
+            .unwrap()
+            .with_compression(true);
+        let chunk = itxt.to_chunk();
+        assert!((chunk.length() as usize) < message.len());
+
+        let parsed = ItxtChunk::try_from(&chunk).unwrap();
+        assert!(parsed.is_compressed());
+        assert_eq!(parsed.text(), message);
+    }
+
+    #[test]
+    fn test_language_tag_validation() {
+        assert!(validate_language_tag("").is_ok());
+        assert!(validate_language_tag("en").is_ok());
+        assert!(validate_language_tag("zh-TW").is_ok());
+        assert!(validate_language_tag("x-klingon").is_ok());
+        assert!(validate_language_tag("en_US").is_err());
+        assert!(validate_language_tag("en-").is_err());
+        assert!(validate_language_tag("toolongtag").is_err());
+    }
+
+    #[test]
+    fn test_itxt_invalid_data() {
+        let chunk = Chunk::new(ChunkType::from_str("iTXt").unwrap(), b"Title\0\0".to_vec());
+        assert!(matches!(
+            ItxtChunk::try_from(&chunk),
+            Err(TextError::Truncated)
+        ));
+
+        let chunk = Chunk::new(
+            ChunkType::from_str("iTXt").unwrap(),
+            b"Title\0\0\0en\0\0\xff".to_vec(),
+        );
+        assert!(matches!(
+            ItxtChunk::try_from(&chunk),
+            Err(TextError::InvalidUtf8(_))
+        ));
+    }
+}
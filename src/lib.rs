@@ -1,7 +1,39 @@
 pub mod chunk;
+pub mod chunk_ref;
 pub mod chunk_type;
+pub mod encryption;
+pub mod filter;
+pub mod fragment;
+pub mod ihdr;
+pub mod image_data;
+pub mod itxt;
+pub mod lsb;
+pub mod payload;
 pub mod png;
+pub mod png_ref;
+pub mod reader;
+pub mod recovery;
+pub mod text;
+pub mod validation;
+pub mod writer;
+pub mod ztxt;
 
 pub use chunk::{Chunk, ChunkError};
-pub use chunk_type::{ChunkType, ChunkTypeError};
-pub use png::{Png, PngError};
+pub use chunk_ref::ChunkRef;
+pub use chunk_type::{ChunkType, ChunkTypeError, TypePolicyError, TypeWarning};
+pub use encryption::EncryptionError;
+pub use filter::{FilterStrategy, FilterType};
+pub use fragment::{Fragment, FragmentError, Reassembler};
+pub use ihdr::{ColorType, Ihdr, IhdrError, InterlaceMethod};
+pub use image_data::{ImageData, ImageDataError, ImageEncodeOptions};
+pub use itxt::ItxtChunk;
+pub use lsb::{ChannelMask, LsbError, LsbOptions, OrderVersion};
+pub use payload::{FilePayload, PayloadError};
+pub use png::{ChunkPosition, CrcFix, Png, PngError};
+pub use png_ref::PngRef;
+pub use reader::ChunkReader;
+pub use recovery::{Diagnostic, ParseOptions, ParseReport};
+pub use text::{TextChunk, TextError};
+pub use validation::Violation;
+pub use writer::PngWriter;
+pub use ztxt::ZtxtChunk;
@@ -0,0 +1,730 @@
+use std::fmt;
+use std::str::FromStr;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+
+use crate::ihdr::{ColorType, Ihdr};
+use crate::image_data::ImageData;
+
+/// Specialized `Result` type for LSB embedding operations.
+pub type Result<T> = std::result::Result<T, LsbError>;
+
+/// Magic bytes marking data hidden in pixel bits.
+pub const MAGIC: [u8; 8] = *b"PNGMBITS";
+
+/// Current version of the LSB header format.
+pub const VERSION: u8 = 1;
+
+/// Size of the header written before the payload: magic, version and payload length.
+pub const HEADER_SIZE: usize = MAGIC.len() + 1 + 4;
+
+/// Errors that can occur when hiding data in pixels or reading it back.
+#[derive(Debug, thiserror::Error)]
+pub enum LsbError {
+    /// Returned for palette images, where changing an index can change the color completely.
+    #[error("Palette images cannot hide data in pixels without visible changes")]
+    PaletteImage,
+
+    /// Returned for bit depths below 8, where every bit is visible.
+    #[error("{0}-bit images cannot hide data in pixels without visible changes")]
+    UnsupportedBitDepth(u8),
+
+    /// Returned when the bits per channel are not between 1 and 8.
+    #[error("Bits per channel must be 1-8, got {0}")]
+    InvalidBitsPerChannel(u8),
+
+    /// Returned when the channel mask selects no channel the image has.
+    #[error("The image has none of the channels '{mask}' ({color_type})")]
+    NoChannels {
+        mask: ChannelMask,
+        color_type: ColorType,
+    },
+
+    /// Returned when a channel mask contains an unknown letter.
+    #[error("Unknown channel '{0}'; use r, g, b, y (gray) or a")]
+    InvalidChannel(char),
+
+    /// Returned when the payload does not fit in the selected bits.
+    #[error("Payload needs {required} bytes but the image can hide only {capacity}")]
+    TooLarge { required: usize, capacity: usize },
+
+    /// Returned when the selected bits do not start with the LSB header.
+    #[error("No data is hidden in the pixels with these settings")]
+    NotFound,
+
+    /// Returned when the header was written by an unknown format version.
+    #[error("Unsupported LSB format version {0}")]
+    UnsupportedVersion(u8),
+
+    /// Returned when the header promises more data than the image can hold.
+    #[error("Hidden data is truncated: header says {expected} bytes, image holds {capacity}")]
+    Truncated { expected: usize, capacity: usize },
+
+    /// Returned when the carrier order cannot be derived from the key.
+    #[error("Key derivation failed: {0}")]
+    KeyDerivation(argon2::Error),
+}
+
+/// Versions of the key-seeded carrier order.
+///
+/// With a key, payload bits are scattered over the carriers in an order
+/// derived from it, so reading them in any other order yields noise. Each
+/// version pins down every step of that derivation, so data hidden today
+/// reads back the same in later releases: a different order gets a new
+/// version rather than changing an old one. [`extract`] tries every
+/// version, since the header is only readable once the order is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderVersion {
+    /// Version 1:
+    ///
+    /// 1. Seed: 32 bytes of Argon2id (version 0x13, 19456 KiB, 2
+    ///    iterations, 1 lane) of the key, salted with `pngme-lsb-order-v1`.
+    /// 2. Generator: xoshiro256**, its state the seed read as four
+    ///    little-endian `u64`s.
+    /// 3. Draws below `n`: the next output modulo `n`, skipping outputs of
+    ///    `2^64 - (2^64 mod n)` or more so every value is equally likely.
+    /// 4. Shuffle: Fisher-Yates over the carriers in pixel order, swapping
+    ///    each index `i` from the last down to 1 with a draw below `i + 1`.
+    V1,
+}
+
+impl OrderVersion {
+    /// The version used for embedding.
+    pub const LATEST: OrderVersion = OrderVersion::V1;
+
+    /// Every version, newest first.
+    pub const ALL: [OrderVersion; 1] = [OrderVersion::V1];
+
+    /// Shuffles `carriers` into the order this version derives from `key`.
+    fn shuffle<T>(&self, carriers: &mut [T], key: &str) -> Result<()> {
+        match self {
+            OrderVersion::V1 => {
+                let params = Params::new(19456, 2, 1, Some(32)).expect("valid Argon2 parameters");
+                let mut seed = [0; 32];
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(key.as_bytes(), b"pngme-lsb-order-v1", &mut seed)
+                    .map_err(LsbError::KeyDerivation)?;
+                let mut rng = Xoshiro256StarStar::from_seed(seed);
+                for i in (1..carriers.len()).rev() {
+                    carriers.swap(i, rng.below(i as u64 + 1) as usize);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The xoshiro256** generator by Blackman and Vigna.
+///
+/// Implemented here rather than taken from a crate so its output can never
+/// change under a dependency update.
+struct Xoshiro256StarStar([u64; 4]);
+
+impl Xoshiro256StarStar {
+    fn from_seed(seed: [u8; 32]) -> Self {
+        let mut state = [0; 4];
+        for (word, bytes) in state.iter_mut().zip(seed.chunks_exact(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().expect("chunks are 8 bytes"));
+        }
+        Self(state)
+    }
+
+    fn next(&mut self) -> u64 {
+        let s = &mut self.0;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// A uniformly distributed value below `n`, which must not be zero.
+    fn below(&mut self, n: u64) -> u64 {
+        // Outputs at or above the largest multiple of n would favor small values
+        let limit = u64::MAX - (u64::MAX % n + 1) % n;
+        loop {
+            let x = self.next();
+            if x <= limit {
+                return x % n;
+            }
+        }
+    }
+}
+
+/// A set of pixel channels to hide data in.
+///
+/// Written as letters: `r`, `g`, `b` and `a` for RGB(A) images, and `y` for
+/// the gray sample of grayscale images. Channels the image does not have
+/// are skipped, so the default (every color channel, no alpha) suits both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelMask(u8);
+
+impl ChannelMask {
+    pub const RED: ChannelMask = ChannelMask(1);
+    pub const GREEN: ChannelMask = ChannelMask(2);
+    pub const BLUE: ChannelMask = ChannelMask(4);
+    pub const GRAY: ChannelMask = ChannelMask(8);
+    pub const ALPHA: ChannelMask = ChannelMask(16);
+
+    /// Letters in the order of the flags above.
+    const LETTERS: [char; 5] = ['r', 'g', 'b', 'y', 'a'];
+
+    /// True if every channel in `other` is also in `self`.
+    pub fn contains(&self, other: ChannelMask) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the union of both masks.
+    pub fn union(self, other: ChannelMask) -> ChannelMask {
+        ChannelMask(self.0 | other.0)
+    }
+
+    /// The image's channels in sample order, each with whether the mask selects it.
+    fn select(&self, color_type: ColorType) -> Vec<bool> {
+        let channels: &[ChannelMask] = match color_type {
+            ColorType::Grayscale | ColorType::Indexed => &[Self::GRAY],
+            ColorType::GrayscaleAlpha => &[Self::GRAY, Self::ALPHA],
+            ColorType::Rgb => &[Self::RED, Self::GREEN, Self::BLUE],
+            ColorType::Rgba => &[Self::RED, Self::GREEN, Self::BLUE, Self::ALPHA],
+        };
+        channels.iter().map(|&c| self.contains(c)).collect()
+    }
+}
+
+/// Every color channel, without alpha.
+impl Default for ChannelMask {
+    fn default() -> Self {
+        Self::RED
+            .union(Self::GREEN)
+            .union(Self::BLUE)
+            .union(Self::GRAY)
+    }
+}
+
+impl FromStr for ChannelMask {
+    type Err = LsbError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.chars().try_fold(ChannelMask(0), |mask, c| {
+            let index = Self::LETTERS
+                .iter()
+                .position(|&l| l == c.to_ascii_lowercase())
+                .ok_or(LsbError::InvalidChannel(c))?;
+            Ok(mask.union(ChannelMask(1 << index)))
+        })
+    }
+}
+
+impl fmt::Display for ChannelMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, letter) in Self::LETTERS.iter().enumerate() {
+            if self.0 & (1 << index) != 0 {
+                write!(f, "{letter}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Which bits of which samples carry hidden data, and in what order.
+///
+/// Decoding needs the same options that were used for encoding.
+///
+/// # Examples
+///
+/// ```ignore
+/// let options = LsbOptions::default()
+///     .with_bits_per_channel(2)?
+///     .with_channels("rg".parse()?)
+///     .with_key("correct horse battery staple");
+/// lsb::embed(&mut image, b"secret", &options)?;
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct LsbOptions {
+    bits_per_channel: u8,
+    channels: ChannelMask,
+    key: Option<String>,
+}
+
+impl LsbOptions {
+    /// Sets how many low bits of each selected sample carry data (1-8).
+    ///
+    /// More bits hold more data but change the image more.
+    pub fn with_bits_per_channel(mut self, bits: u8) -> Result<Self> {
+        if !(1..=8).contains(&bits) {
+            return Err(LsbError::InvalidBitsPerChannel(bits));
+        }
+        self.bits_per_channel = bits;
+        Ok(self)
+    }
+
+    /// Sets which channels carry data.
+    pub fn with_channels(mut self, channels: ChannelMask) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Scatters the data over the carriers in an order derived from `key`.
+    ///
+    /// See [`OrderVersion`] for how the order is derived.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// How many low bits of each selected sample carry data.
+    pub fn bits_per_channel(&self) -> u8 {
+        self.bits_per_channel
+    }
+
+    /// Which channels carry data.
+    pub fn channels(&self) -> ChannelMask {
+        self.channels
+    }
+
+    /// The key the carrier order is derived from, if any.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+}
+
+/// Shows whether a key is set without revealing it.
+impl fmt::Debug for LsbOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LsbOptions")
+            .field("bits_per_channel", &self.bits_per_channel)
+            .field("channels", &self.channels)
+            .field("key", &self.key.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+/// One bit per sample in every color channel.
+impl Default for LsbOptions {
+    fn default() -> Self {
+        Self {
+            bits_per_channel: 1,
+            channels: ChannelMask::default(),
+            key: None,
+        }
+    }
+}
+
+/// Number of payload bytes that fit in an image with this header.
+///
+/// The LSB header is already accounted for.
+pub fn capacity(header: &Ihdr, options: &LsbOptions) -> Result<usize> {
+    let bits = Layout::new(header, options)?.len() * options.bits_per_channel as usize;
+    Ok((bits / 8).saturating_sub(HEADER_SIZE))
+}
+
+/// Hides `payload` in the low bits of the image's samples.
+///
+/// A header with the payload length is written first, so [`extract`] knows
+/// where the data ends. Samples are used in pixel order, or with a key in
+/// the order [`OrderVersion::LATEST`] derives from it.
+pub fn embed(image: &mut ImageData, payload: &[u8], options: &LsbOptions) -> Result<()> {
+    let layout = Layout::new(image.header(), options)?;
+    let capacity =
+        (layout.len() * options.bits_per_channel as usize / 8).saturating_sub(HEADER_SIZE);
+    if payload.len() > capacity || payload.len() > u32::MAX as usize {
+        return Err(LsbError::TooLarge {
+            required: payload.len(),
+            capacity,
+        });
+    }
+
+    let mut data = Vec::with_capacity(HEADER_SIZE + payload.len());
+    data.extend_from_slice(&MAGIC);
+    data.push(VERSION);
+    data.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    data.extend_from_slice(payload);
+    let carriers = match options.key() {
+        Some(key) => Carriers::keyed(layout, OrderVersion::LATEST, key)?,
+        None => Carriers::in_pixel_order(layout),
+    };
+
+    let bits = options.bits_per_channel as usize;
+    let mask = low_bits(bits);
+    let pixels = image.pixels_mut();
+    for n in 0..(data.len() * 8).div_ceil(bits) {
+        let index = carriers.position(n);
+        pixels[index] = (pixels[index] & !mask) | read_bits(&data, n * bits, bits);
+    }
+    Ok(())
+}
+
+/// Reads back data hidden by [`embed`] with the same options.
+///
+/// With a key, every [`OrderVersion`] is tried until one finds the header.
+pub fn extract(image: &ImageData, options: &LsbOptions) -> Result<Vec<u8>> {
+    let layout = Layout::new(image.header(), options)?;
+    let Some(key) = options.key() else {
+        return extract_from(
+            image,
+            &Carriers::in_pixel_order(layout),
+            options.bits_per_channel,
+        );
+    };
+    for version in OrderVersion::ALL {
+        let carriers = Carriers::keyed(layout.clone(), version, key)?;
+        match extract_from(image, &carriers, options.bits_per_channel) {
+            Err(LsbError::NotFound) => continue,
+            result => return result,
+        }
+    }
+    Err(LsbError::NotFound)
+}
+
+/// Reads the header and payload from `carriers`.
+fn extract_from(image: &ImageData, carriers: &Carriers, bits: u8) -> Result<Vec<u8>> {
+    let bits = bits as usize;
+    let pixels = image.pixels();
+    let read = |start: usize, len: usize| -> Vec<u8> {
+        let mut bytes = vec![0; len];
+        let first = start * 8 / bits;
+        let last = ((start + len) * 8).div_ceil(bits).min(carriers.len());
+        for n in first..last {
+            write_bits(
+                &mut bytes,
+                (n * bits) as isize - (start * 8) as isize,
+                bits,
+                pixels[carriers.position(n)],
+            );
+        }
+        bytes
+    };
+
+    let capacity = carriers.len() * bits / 8;
+    if capacity < HEADER_SIZE {
+        return Err(LsbError::NotFound);
+    }
+    let header = read(0, HEADER_SIZE);
+    if header[..MAGIC.len()] != MAGIC {
+        return Err(LsbError::NotFound);
+    }
+    let version = header[MAGIC.len()];
+    if version != VERSION {
+        return Err(LsbError::UnsupportedVersion(version));
+    }
+    let length = u32::from_be_bytes(
+        header[MAGIC.len() + 1..]
+            .try_into()
+            .expect("length field is exactly 4 bytes"),
+    ) as usize;
+    if length > capacity - HEADER_SIZE {
+        return Err(LsbError::Truncated {
+            expected: length,
+            capacity: capacity - HEADER_SIZE,
+        });
+    }
+    Ok(read(HEADER_SIZE, length))
+}
+
+/// Where the samples whose low bits carry data lie in the pixel data.
+///
+/// Carrier `n` is the nth selected sample in pixel order. Positions are
+/// computed on demand rather than listed, since an image has millions.
+#[derive(Clone)]
+struct Layout {
+    /// Indices of the selected channels within a pixel
+    selected: Vec<usize>,
+    channels: usize,
+    sample_bytes: usize,
+    len: usize,
+}
+
+impl Layout {
+    fn new(header: &Ihdr, options: &LsbOptions) -> Result<Self> {
+        if header.color_type() == ColorType::Indexed {
+            return Err(LsbError::PaletteImage);
+        }
+        if header.bit_depth() < 8 {
+            return Err(LsbError::UnsupportedBitDepth(header.bit_depth()));
+        }
+
+        let channels = options.channels.select(header.color_type());
+        let selected: Vec<usize> = (0..channels.len()).filter(|&c| channels[c]).collect();
+        if selected.is_empty() {
+            return Err(LsbError::NoChannels {
+                mask: options.channels,
+                color_type: header.color_type(),
+            });
+        }
+
+        let pixels = header.width() as usize * header.height() as usize;
+        Ok(Self {
+            len: pixels * selected.len(),
+            selected,
+            channels: channels.len(),
+            sample_bytes: header.bit_depth() as usize / 8,
+        })
+    }
+
+    /// Number of carriers.
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Byte position of the least significant byte of carrier `n`.
+    fn position(&self, n: usize) -> usize {
+        let pixel = n / self.selected.len();
+        let sample = pixel * self.channels + self.selected[n % self.selected.len()];
+        // 16-bit samples are big-endian, so the low byte comes second
+        sample * self.sample_bytes + self.sample_bytes - 1
+    }
+}
+
+/// The carriers in the order data is written to them.
+struct Carriers {
+    layout: Layout,
+    order: Order,
+}
+
+/// Which carrier holds each successive group of bits.
+enum Order {
+    /// Carrier `n` holds group `n`
+    Sequential,
+    /// Shuffled carrier numbers, stored as `u32` to save memory when they fit
+    Shuffled32(Vec<u32>),
+    /// Shuffled carrier numbers for images with more than `u32::MAX` carriers
+    Shuffled(Vec<usize>),
+}
+
+impl Carriers {
+    fn in_pixel_order(layout: Layout) -> Self {
+        Self {
+            layout,
+            order: Order::Sequential,
+        }
+    }
+
+    fn keyed(layout: Layout, version: OrderVersion, key: &str) -> Result<Self> {
+        let order = match u32::try_from(layout.len()) {
+            Ok(len) => {
+                let mut order: Vec<u32> = (0..len).collect();
+                version.shuffle(&mut order, key)?;
+                Order::Shuffled32(order)
+            }
+            Err(_) => {
+                let mut order: Vec<usize> = (0..layout.len()).collect();
+                version.shuffle(&mut order, key)?;
+                Order::Shuffled(order)
+            }
+        };
+        Ok(Self { layout, order })
+    }
+
+    fn len(&self) -> usize {
+        self.layout.len()
+    }
+
+    /// Byte position in the pixel data of the `n`th carrier to use.
+    fn position(&self, n: usize) -> usize {
+        let carrier = match &self.order {
+            Order::Sequential => n,
+            Order::Shuffled32(order) => order[n] as usize,
+            Order::Shuffled(order) => order[n],
+        };
+        self.layout.position(carrier)
+    }
+}
+
+/// A mask of the lowest `bits` bits.
+fn low_bits(bits: usize) -> u8 {
+    (0xffu16 >> (8 - bits)) as u8
+}
+
+/// Reads `len` (1-8) bits starting at bit `start` of `data`, most significant first.
+///
+/// Bits past the end of `data` read as zero.
+fn read_bits(data: &[u8], start: usize, len: usize) -> u8 {
+    (start..start + len).fold(0, |value, bit| {
+        let set = data
+            .get(bit / 8)
+            .is_some_and(|byte| byte & (0x80 >> (bit % 8)) != 0);
+        (value << 1) | set as u8
+    })
+}
+
+/// Writes the low `len` bits of `value` into `data` starting at bit `start`.
+///
+/// Bits that fall outside `data` (including before it) are dropped.
+fn write_bits(data: &mut [u8], start: isize, len: usize, value: u8) {
+    for i in 0..len {
+        let bit = start + i as isize;
+        if bit < 0 || bit as usize >= data.len() * 8 {
+            continue;
+        }
+        let bit = bit as usize;
+        if value & (1 << (len - 1 - i)) != 0 {
+            data[bit / 8] |= 0x80 >> (bit % 8);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ihdr::InterlaceMethod;
+    use crate::image_data::tests::sample_pixels;
+
+    fn image(width: u32, height: u32, bit_depth: u8, color_type: ColorType) -> ImageData {
+        let header =
+            Ihdr::new(width, height, bit_depth, color_type, InterlaceMethod::None).unwrap();
+        ImageData::new(header, sample_pixels(&header)).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for bits in 1..=8 {
+            let mut image = image(16, 16, 8, ColorType::Rgba);
+            let options = LsbOptions::default().with_bits_per_channel(bits).unwrap();
+            embed(&mut image, b"Meet me at midnight", &options).unwrap();
+            assert_eq!(extract(&image, &options).unwrap(), b"Meet me at midnight");
+        }
+    }
+
+    #[test]
+    fn test_only_touches_selected_low_bits() {
+        let original = image(8, 8, 8, ColorType::Rgba);
+        let mut image = original.clone();
+        let options = LsbOptions::default()
+            .with_bits_per_channel(2)
+            .unwrap()
+            .with_channels("gb".parse().unwrap());
+        embed(&mut image, b"hi", &options).unwrap();
+
+        for (i, (&before, &after)) in original.pixels().iter().zip(image.pixels()).enumerate() {
+            match i % 4 {
+                1 | 2 => assert_eq!(before & !0b11, after & !0b11),
+                _ => assert_eq!(before, after),
+            }
+        }
+        assert_eq!(extract(&image, &options).unwrap(), b"hi");
+    }
+
+    #[test]
+    fn test_sixteen_bit_uses_low_byte() {
+        let original = image(16, 16, 16, ColorType::Grayscale);
+        let mut image = original.clone();
+        embed(&mut image, b"deep", &LsbOptions::default()).unwrap();
+        for (before, after) in original.pixels().chunks(2).zip(image.pixels().chunks(2)) {
+            assert_eq!(before[0], after[0]);
+        }
+        assert_eq!(extract(&image, &LsbOptions::default()).unwrap(), b"deep");
+    }
+
+    #[test]
+    fn test_capacity() {
+        // 10x10 RGB, 1 bit per channel: 300 bits = 37 bytes, minus the header
+        let image = image(10, 10, 8, ColorType::Rgb);
+        let options = LsbOptions::default();
+        assert_eq!(
+            capacity(image.header(), &options).unwrap(),
+            37 - HEADER_SIZE
+        );
+
+        let mut image = image;
+        let payload = vec![0xa5; 37 - HEADER_SIZE + 1];
+        assert!(matches!(
+            embed(&mut image, &payload, &options),
+            Err(LsbError::TooLarge { .. })
+        ));
+        embed(&mut image, &payload[1..], &options).unwrap();
+        assert_eq!(extract(&image, &options).unwrap(), payload[1..]);
+    }
+
+    #[test]
+    fn test_refuses_palette_and_packed_images() {
+        let mut palette = image(8, 8, 8, ColorType::Indexed);
+        assert!(matches!(
+            embed(&mut palette, b"x", &LsbOptions::default()),
+            Err(LsbError::PaletteImage)
+        ));
+        let packed = image(8, 8, 4, ColorType::Grayscale);
+        assert!(matches!(
+            extract(&packed, &LsbOptions::default()),
+            Err(LsbError::UnsupportedBitDepth(4))
+        ));
+    }
+
+    #[test]
+    fn test_missing_or_mismatched_data() {
+        let mut image = image(16, 16, 8, ColorType::Rgb);
+        assert!(matches!(
+            extract(&image, &LsbOptions::default()),
+            Err(LsbError::NotFound)
+        ));
+
+        embed(&mut image, b"secret", &LsbOptions::default()).unwrap();
+        let other = LsbOptions::default().with_bits_per_channel(2).unwrap();
+        assert!(matches!(extract(&image, &other), Err(LsbError::NotFound)));
+    }
+
+    #[test]
+    fn test_keyed_round_trip() {
+        let original = image(16, 16, 8, ColorType::Rgb);
+        let mut image = original.clone();
+        let options = LsbOptions::default().with_key("correct horse");
+        embed(&mut image, b"scattered", &options).unwrap();
+        assert_eq!(extract(&image, &options).unwrap(), b"scattered");
+
+        // The bits are not in pixel order, and another key finds nothing
+        assert!(matches!(
+            extract(&image, &LsbOptions::default()),
+            Err(LsbError::NotFound)
+        ));
+        let wrong = LsbOptions::default().with_key("battery staple");
+        assert!(matches!(extract(&image, &wrong), Err(LsbError::NotFound)));
+
+        // Changes are spread over the image rather than packed at the start
+        let changed: Vec<usize> = (0..original.pixels().len())
+            .filter(|&i| original.pixels()[i] != image.pixels()[i])
+            .collect();
+        assert!(changed.last().unwrap() > &(original.pixels().len() / 2));
+    }
+
+    #[test]
+    fn test_order_v1_is_stable() {
+        // Data hidden with version 1 must read back the same forever
+        let mut carriers: Vec<usize> = (0..10).collect();
+        OrderVersion::V1.shuffle(&mut carriers, "pngme").unwrap();
+        assert_eq!(carriers, [8, 6, 7, 5, 0, 2, 9, 1, 4, 3]);
+    }
+
+    #[test]
+    fn test_xoshiro_reference_output() {
+        // First outputs of the reference implementation seeded with 1, 2, 3, 4
+        let mut seed = [0; 32];
+        for (i, word) in seed.chunks_exact_mut(8).enumerate() {
+            word.copy_from_slice(&(i as u64 + 1).to_le_bytes());
+        }
+        let mut rng = Xoshiro256StarStar::from_seed(seed);
+        let outputs: Vec<u64> = (0..4).map(|_| rng.next()).collect();
+        assert_eq!(outputs, [11520, 0, 1509978240, 1215971899390074240]);
+    }
+
+    #[test]
+    fn test_channel_mask() {
+        let mask: ChannelMask = "RgA".parse().unwrap();
+        assert_eq!(mask.to_string(), "rga");
+        assert!(mask.contains(ChannelMask::ALPHA));
+        assert!(!mask.contains(ChannelMask::BLUE));
+        assert!(matches!(
+            "rx".parse::<ChannelMask>(),
+            Err(LsbError::InvalidChannel('x'))
+        ));
+
+        let mut gray = image(8, 8, 8, ColorType::Grayscale);
+        let options = LsbOptions::default().with_channels("rgb".parse().unwrap());
+        assert!(matches!(
+            embed(&mut gray, b"x", &options),
+            Err(LsbError::NoChannels { .. })
+        ));
+    }
+}
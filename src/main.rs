@@ -1,11 +1,17 @@
 mod args;
 mod commands;
+mod inspect;
 mod png_file;
+mod report;
 
+use std::path::Path;
 use std::process;
 
-use crate::args::{Cli, Commands};
-use clap::Parser;
+use crate::args::{Cli, Commands, Method};
+use crate::commands::{Decoded, EncodeOptions, Message, Selection, TextKey};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use pngme::{ChunkPosition, ImageEncodeOptions};
 
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -26,22 +32,210 @@ fn run() -> Result<()> {
             chunk_type,
             message,
             output_file,
-        } => commands::encode(&file_path, chunk_type, message, output_file.as_deref())?,
+            file,
+            output,
+            position,
+            index,
+            keyword,
+            lang,
+            encrypt,
+            split,
+            force,
+            method,
+            text,
+            lsb,
+        } => match (method, chunk_type) {
+            (Method::Chunk, Some(chunk_type)) => {
+                let message = message_source(message.as_deref(), file.as_deref());
+                let options = EncodeOptions {
+                    position: index
+                        .map(ChunkPosition::Index)
+                        .unwrap_or_else(|| (*position).into()),
+                    text_key: keyword.as_deref().map(|keyword| TextKey {
+                        keyword,
+                        language_tag: lang.as_deref(),
+                    }),
+                    encrypt: *encrypt,
+                    split: split.map(|size| size as usize),
+                    force: *force,
+                };
+                commands::encode(
+                    file_path,
+                    chunk_type,
+                    message,
+                    output_file.as_deref().or(output.as_deref()),
+                    &options,
+                )?
+            }
+            (Method::Lsb, None) => commands::encode_lsb(
+                file_path,
+                message_source(text.as_deref(), file.as_deref()),
+                output.as_deref(),
+                *encrypt,
+                lsb.key,
+                &lsb.options(),
+            )?,
+            (Method::Lsb, Some(chunk_type)) => lsb_chunk_type_conflict(chunk_type),
+            (Method::Chunk, None) => unreachable!("clap requires a chunk type"),
+        },
         Commands::Decode {
             file_path,
             chunk_type,
-        } => match commands::decode(&file_path, chunk_type) {
-            Ok(msg) => println!("{}", msg),
-            Err(commands::CommandsError::ChunkNotFound(_)) => {
-                println!("No chunk with type: {chunk_type}")
+            keyword,
+            lang,
+            output,
+            all,
+            index,
+            method,
+            lsb,
+        } => match (method, chunk_type) {
+            (Method::Chunk, Some(chunk_type)) => {
+                let text_key = keyword.as_deref().map(|keyword| TextKey {
+                    keyword,
+                    language_tag: lang.as_deref(),
+                });
+                let selection = selection(*all, *index);
+                match commands::decode(
+                    file_path,
+                    chunk_type,
+                    text_key,
+                    selection,
+                    output.as_deref(),
+                ) {
+                    Ok(decoded) => decoded.into_iter().for_each(print_decoded),
+                    Err(commands::CommandsError::ChunkNotFound(what)) => {
+                        println!("No chunk with type: {what}")
+                    }
+                    Err(e) => return Err(e.into()),
+                }
             }
-            Err(e) => return Err(e.into()),
+            (Method::Lsb, None) => print_decoded(commands::decode_lsb(
+                file_path,
+                &lsb.options(),
+                lsb.key,
+                output.as_deref(),
+            )?),
+            (Method::Lsb, Some(chunk_type)) => lsb_chunk_type_conflict(chunk_type),
+            (Method::Chunk, None) => unreachable!("clap requires a chunk type"),
         },
         Commands::Remove {
             file_path,
             chunk_type,
-        } => commands::remove(&file_path, chunk_type)?,
-        Commands::Print { file_path } => commands::print(&file_path)?,
+            all,
+            index,
+        } => {
+            let removed = commands::remove(file_path, chunk_type, selection(*all, *index))?;
+            println!("Removed {removed} {chunk_type} chunk(s)");
+        }
+        Commands::Print { file_path, format } => commands::print(file_path, *format)?,
+        Commands::Inspect {
+            file_path,
+            chunk,
+            limit,
+        } => {
+            let limit = (*limit > 0).then_some(*limit);
+            print!("{}", commands::inspect(file_path, *chunk, limit)?);
+        }
+        Commands::Validate { file_path } => {
+            let violations = commands::validate(file_path)?;
+            if !violations.is_empty() {
+                for violation in &violations {
+                    println!("{violation}");
+                }
+                return Err(commands::CommandsError::InvalidStructure(violations.len()).into());
+            }
+            println!("OK");
+        }
+        Commands::FixCrc {
+            file_path,
+            output,
+            dry_run,
+        } => {
+            let fixes = commands::fix_crc(file_path, output.as_deref(), *dry_run)?;
+            for fix in &fixes {
+                println!("{fix}");
+            }
+            match (fixes.len(), dry_run) {
+                (0, _) => println!("All CRCs are correct"),
+                (n, true) => println!("{n} CRC(s) would be fixed"),
+                (n, false) => println!("Fixed {n} CRC(s)"),
+            }
+        }
+        Commands::ExtractTrailer { file_path, output } => {
+            let (path, size) = commands::extract_trailer(file_path, output.as_deref())?;
+            println!("Saved {size} bytes to {}", path.display());
+        }
+        Commands::StripTrailer { file_path, output } => {
+            let removed = commands::strip_trailer(file_path, output.as_deref())?;
+            println!("Removed {removed} bytes after IEND");
+        }
+        Commands::Recompress {
+            file_path,
+            output,
+            interlace,
+            filter,
+            level,
+            max_chunk_size,
+        } => {
+            let mut options = ImageEncodeOptions::default()
+                .with_filter((*filter).into())
+                .with_level(*level)
+                .with_max_chunk_size(*max_chunk_size as usize);
+            if let Some(interlace) = interlace {
+                options = options.with_interlace((*interlace).into());
+            }
+            let (before, after) = commands::recompress(file_path, output.as_deref(), &options)?;
+            println!("Image data: {before} -> {after} bytes");
+        }
+        Commands::Recover { file_path, output } => {
+            let recovery = commands::recover(file_path, output.as_deref())?;
+            for diagnostic in &recovery.diagnostics {
+                println!("{diagnostic}");
+            }
+            println!(
+                "Recovered {} chunk(s) to {}",
+                recovery.chunks,
+                recovery.path.display()
+            );
+        }
     };
     Ok(())
 }
+
+/// Maps the `--all` and `--index` flags to a chunk selection.
+fn selection(all: bool, index: Option<usize>) -> Selection {
+    match (all, index) {
+        (_, Some(index)) => Selection::Index(index),
+        (true, None) => Selection::All,
+        (false, None) => Selection::First,
+    }
+}
+
+/// Picks the message to encode from the message text or `--file`.
+fn message_source<'a>(text: Option<&'a str>, file: Option<&'a Path>) -> Message<'a> {
+    match (text, file) {
+        (_, Some(path)) => Message::File(path),
+        (Some(text), None) => Message::Text(text),
+        (None, None) => unreachable!("clap requires a message or --file"),
+    }
+}
+
+fn print_decoded(decoded: Decoded) {
+    match decoded {
+        Decoded::Message(msg) => println!("{}", msg),
+        Decoded::Saved { path, size } => println!("Saved {size} bytes to {}", path.display()),
+    }
+}
+
+/// Rejects a chunk type given with `--method lsb`.
+///
+/// Clap cannot make an argument conflict with one value of another, so
+/// this is the one rule the argument definitions leave to us.
+fn lsb_chunk_type_conflict(chunk_type: &str) -> ! {
+    Cli::command()
+        .error(
+            ErrorKind::ArgumentConflict,
+            format!("no chunk type is used with --method lsb, got '{chunk_type}'"),
+        )
+        .exit()
+}
@@ -0,0 +1,221 @@
+use std::path::Path;
+
+/// Specialized `Result` type for payload operations.
+pub type Result<T> = std::result::Result<T, PayloadError>;
+
+/// Magic bytes marking an embedded file payload.
+pub const MAGIC: [u8; 8] = *b"PNGMFILE";
+
+/// Current version of the file payload format.
+pub const VERSION: u8 = 1;
+
+/// An arbitrary binary file together with the metadata needed to restore it.
+///
+/// Serialized payloads are laid out as:
+/// - 8 bytes: Magic "PNGMFILE"
+/// - 1 byte: Format version (1)
+/// - 2 bytes: File name length (big-endian u16)
+/// - N bytes: File name (UTF-8, no directory components)
+/// - 8 bytes: File size (big-endian u64)
+/// - M bytes: File contents
+///
+/// # Examples
+///
+/// ```ignore
+/// let payload = FilePayload::from_path("report.pdf")?;
+/// let chunk = Chunk::new(chunk_type, payload.to_bytes());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePayload {
+    file_name: String,
+    data: Vec<u8>,
+}
+
+/// Errors that can occur when building or parsing a file payload.
+#[derive(Debug, thiserror::Error)]
+pub enum PayloadError {
+    /// Returned when the bytes do not start with the file payload magic.
+    #[error("Data is not an embedded file")]
+    NotAFilePayload,
+
+    /// Returned when the payload was written by an unknown format version.
+    #[error("Unsupported file payload version {0}")]
+    UnsupportedVersion(u8),
+
+    /// Returned when the payload ends before a required field.
+    #[error("File payload is truncated")]
+    Truncated,
+
+    /// Returned when the stored size disagrees with the number of data bytes.
+    #[error("File size mismatch: header says {expected} bytes, found {actual}")]
+    SizeMismatch { expected: u64, actual: u64 },
+
+    /// Returned when a file name is empty, too long, not UTF-8 or contains a path.
+    #[error("Invalid file name '{0}'")]
+    InvalidFileName(String),
+
+    /// Returned when reading the source file fails.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl FilePayload {
+    /// Creates a payload from a file name and its contents.
+    ///
+    /// The name must be a bare file name: directory separators and `..` are
+    /// rejected so extraction cannot write outside the chosen directory.
+    pub fn new(file_name: &str, data: Vec<u8>) -> Result<Self> {
+        validate_file_name(file_name)?;
+        Ok(Self {
+            file_name: file_name.to_string(),
+            data,
+        })
+    }
+
+    /// Reads a file from disk, keeping only the final component of its path as the name.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| PayloadError::InvalidFileName(path.display().to_string()))?;
+        let data = std::fs::read(path)?;
+        Self::new(file_name, data)
+    }
+
+    /// The original name of the embedded file.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// The contents of the embedded file.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Serializes the payload with its header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let name = self.file_name.as_bytes();
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 1 + 2 + name.len() + 8 + self.data.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(name);
+        bytes.extend_from_slice(&(self.data.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+}
+
+/// Attempts to parse a file payload, checking the stored size against the data.
+impl TryFrom<&[u8]> for FilePayload {
+    type Error = PayloadError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        let rest = bytes
+            .strip_prefix(&MAGIC)
+            .ok_or(PayloadError::NotAFilePayload)?;
+        let (&version, rest) = rest.split_first().ok_or(PayloadError::Truncated)?;
+        if version != VERSION {
+            return Err(PayloadError::UnsupportedVersion(version));
+        }
+
+        let (name_length, rest) = rest
+            .split_first_chunk::<2>()
+            .ok_or(PayloadError::Truncated)?;
+        let name_length = u16::from_be_bytes(*name_length) as usize;
+        if rest.len() < name_length {
+            return Err(PayloadError::Truncated);
+        }
+        let (name, rest) = rest.split_at(name_length);
+        let file_name = std::str::from_utf8(name)
+            .map_err(|_| PayloadError::InvalidFileName(String::from_utf8_lossy(name).into()))?;
+
+        let (size, data) = rest
+            .split_first_chunk::<8>()
+            .ok_or(PayloadError::Truncated)?;
+        let size = u64::from_be_bytes(*size);
+        if size != data.len() as u64 {
+            return Err(PayloadError::SizeMismatch {
+                expected: size,
+                actual: data.len() as u64,
+            });
+        }
+
+        Self::new(file_name, data.to_vec())
+    }
+}
+
+/// True if the bytes start with the file payload magic.
+pub fn is_file_payload(bytes: &[u8]) -> bool {
+    bytes.starts_with(&MAGIC)
+}
+
+/// Checks that a file name is a single, non-empty path component of at most 65535 bytes.
+fn validate_file_name(file_name: &str) -> Result<()> {
+    let invalid = file_name.is_empty()
+        || file_name.len() > u16::MAX as usize
+        || file_name == "."
+        || file_name == ".."
+        || file_name.contains(['/', '\\', '\0']);
+    if invalid {
+        return Err(PayloadError::InvalidFileName(file_name.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_payload_round_trip() {
+        let data: Vec<u8> = (0..=255).collect();
+        let payload = FilePayload::new("key.bin", data.clone()).unwrap();
+        let bytes = payload.to_bytes();
+        assert!(is_file_payload(&bytes));
+
+        let parsed = FilePayload::try_from(&bytes[..]).unwrap();
+        assert_eq!(parsed.file_name(), "key.bin");
+        assert_eq!(parsed.data(), &data[..]);
+    }
+
+    #[test]
+    fn test_file_payload_invalid_name() {
+        assert!(FilePayload::new("", vec![]).is_err());
+        assert!(FilePayload::new("..", vec![]).is_err());
+        assert!(FilePayload::new("../etc/passwd", vec![]).is_err());
+        assert!(FilePayload::new("dir\\file", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_file_payload_size_mismatch() {
+        let mut bytes = FilePayload::new("a.txt", b"hello".to_vec())
+            .unwrap()
+            .to_bytes();
+        bytes.pop();
+        assert!(matches!(
+            FilePayload::try_from(&bytes[..]),
+            Err(PayloadError::SizeMismatch {
+                expected: 5,
+                actual: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_file_payload_invalid_header() {
+        assert!(matches!(
+            FilePayload::try_from(&b"hello"[..]),
+            Err(PayloadError::NotAFilePayload)
+        ));
+        assert!(matches!(
+            FilePayload::try_from(&b"PNGMFILE\x01\x00"[..]),
+            Err(PayloadError::Truncated)
+        ));
+        assert!(matches!(
+            FilePayload::try_from(&b"PNGMFILE\x02"[..]),
+            Err(PayloadError::UnsupportedVersion(2))
+        ));
+    }
+}
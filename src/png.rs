@@ -1,8 +1,19 @@
-use std::{fmt::Display, str::FromStr};
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    str::FromStr,
+};
 
 use crate::{
     chunk::{Chunk, ChunkError},
     chunk_type::{ChunkType, ChunkTypeError},
+    ihdr::{Ihdr, IhdrError},
+    image_data::{ImageData, ImageDataError, ImageEncodeOptions},
+    png_ref::PngRef,
+    reader::ChunkReader,
+    recovery::{self, ParseOptions, ParseReport},
+    validation::{self, Violation},
+    writer::PngWriter,
 };
 
 /// Type alias for PNG operation results
@@ -13,9 +24,12 @@ pub type Result<T> = std::result::Result<T, PngError>;
 /// A PNG file consists of:
 /// - A fixed 8-byte signature header
 /// - One or more chunks containing image data and metadata
+/// - Optionally, trailing bytes after the IEND chunk
 pub struct Png {
     /// Vector of chunks that make up this PNG image
     chunks: Vec<Chunk>,
+    /// Bytes that followed the IEND chunk in the source file
+    trailer: Vec<u8>,
 }
 
 /// Errors that can occur during PNG parsing and manipulation.
@@ -62,6 +76,72 @@ pub enum PngError {
     /// Returned when a requested chunk type is not found in the PNG.
     #[error("Chunk not found: type '{chunk_type}'")]
     ChunkNotFound { chunk_type: String },
+
+    /// Returned when the IHDR chunk cannot be decoded.
+    #[error("Invalid IHDR chunk: {0}")]
+    InvalidIhdr(#[from] IhdrError),
+
+    /// Returned when a chunk index is outside the chunk list.
+    #[error("Chunk index {index} out of bounds for {len} chunks")]
+    IndexOutOfBounds { index: usize, len: usize },
+
+    /// Returned when a chunk would be inserted after IEND, where parsers stop reading.
+    #[error("Chunk index {index} is after IEND, where it would be read as trailer data")]
+    AfterIend { index: usize },
+
+    /// Returned when the IDAT stream cannot be decoded into scanlines.
+    #[error("Invalid image data: {0}")]
+    InvalidImageData(#[from] ImageDataError),
+
+    /// Returned when reading a PNG stream fails.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A chunk whose stored CRC did not match its contents.
+///
+/// Reported by [`Png::bad_crcs`] and [`Png::recompute_crcs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrcFix {
+    /// Position of the chunk in `Png::chunks()`
+    pub index: usize,
+    pub chunk_type: ChunkType,
+    /// The CRC stored in the file
+    pub old: u32,
+    /// The CRC computed from the chunk type and data
+    pub new: u32,
+}
+
+impl Display for CrcFix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Chunk {} ({}): CRC 0x{:08X} -> 0x{:08X}",
+            self.index, self.chunk_type, self.old, self.new
+        )
+    }
+}
+
+/// Where a new chunk should be placed within a PNG's chunk list.
+///
+/// Ancillary chunks written after IEND are treated as trailing garbage by many
+/// decoders and optimizers, so `BeforeIend` is the default placement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkPosition {
+    /// Immediately before the IEND chunk
+    #[default]
+    BeforeIend,
+    /// Immediately after the IHDR chunk
+    AfterIhdr,
+    /// Immediately before the first IDAT chunk
+    BeforeFirstIdat,
+    /// At the given index in the chunk list (`len` appends)
+    Index(usize),
+    /// After every existing chunk
+    ///
+    /// Refused once the PNG has an IEND chunk: parsers stop there, so the
+    /// chunk would come back as part of the [trailer](Png::trailer).
+    End,
 }
 
 impl Png {
@@ -85,8 +165,46 @@ impl Png {
     ///
     /// This does not validate that the chunks form a valid PNG according to the PNG specification.
     /// For example, it doesn't verify that required chunks like IHDR are present and in the correct order.
+    /// Use [`Png::validate`] for that.
     pub fn from_chunks(chunks: Vec<Chunk>) -> Self {
-        Png { chunks }
+        Png {
+            chunks,
+            trailer: Vec::new(),
+        }
+    }
+
+    /// Byte offset of each chunk in the serialized file, in chunk order.
+    ///
+    /// The first chunk starts right after the 8-byte signature.
+    pub fn chunk_offsets(&self) -> Vec<usize> {
+        self.chunks
+            .iter()
+            .scan(Self::STANDARD_HEADER.len(), |offset, chunk| {
+                let start = *offset;
+                *offset +=
+                    Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE + chunk.data().len() + Chunk::CRC_SIZE;
+                Some(start)
+            })
+            .collect()
+    }
+
+    /// Returns the bytes that followed the IEND chunk, if any.
+    ///
+    /// Parsing stops at IEND; anything after it (appended archives,
+    /// polyglot payloads, junk) is kept here untouched and written back
+    /// after the chunks on save.
+    pub fn trailer(&self) -> &[u8] {
+        &self.trailer
+    }
+
+    /// Replaces the bytes written after the last chunk.
+    pub fn set_trailer(&mut self, trailer: Vec<u8>) {
+        self.trailer = trailer;
+    }
+
+    /// Removes the trailer and returns it.
+    pub fn take_trailer(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.trailer)
     }
 
     /// Appends a chunk to the end of this PNG's chunk list.
@@ -98,6 +216,83 @@ impl Png {
         self.chunks.push(chunk);
     }
 
+    /// Inserts a chunk at the requested position.
+    ///
+    /// # Parameters
+    ///
+    /// * `position` - Where the chunk should be placed
+    /// * `chunk` - The chunk to insert
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The index the chunk was inserted at
+    /// * `Err(PngError::ChunkNotFound)` - If the anchor chunk (IHDR, IDAT or IEND) is missing
+    /// * `Err(PngError::IndexOutOfBounds)` - If an explicit index is past the end of the list
+    /// * `Err(PngError::AfterIend)` - If the chunk would land after IEND
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let index = png.insert_chunk(ChunkPosition::BeforeIend, chunk)?;
+    /// ```
+    pub fn insert_chunk(&mut self, position: ChunkPosition, chunk: Chunk) -> Result<usize> {
+        let index = match position {
+            ChunkPosition::BeforeIend => self.position_of("IEND")?,
+            ChunkPosition::AfterIhdr => self.position_of("IHDR")? + 1,
+            ChunkPosition::BeforeFirstIdat => self.position_of("IDAT")?,
+            ChunkPosition::Index(index) => index,
+            ChunkPosition::End => self.chunks.len(),
+        };
+        self.insert_chunk_at(index, chunk)?;
+        Ok(index)
+    }
+
+    /// Inserts a chunk at the given index, shifting later chunks back.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the chunk was inserted
+    /// * `Err(PngError::IndexOutOfBounds)` - If `index` is greater than the number of chunks
+    /// * `Err(PngError::AfterIend)` - If `index` is after an IEND chunk
+    pub fn insert_chunk_at(&mut self, index: usize, chunk: Chunk) -> Result<()> {
+        if index > self.chunks.len() {
+            return Err(PngError::IndexOutOfBounds {
+                index,
+                len: self.chunks.len(),
+            });
+        }
+        if matches!(self.position_of("IEND"), Ok(iend) if index > iend) {
+            return Err(PngError::AfterIend { index });
+        }
+        self.chunks.insert(index, chunk);
+        Ok(())
+    }
+
+    /// Inserts a chunk immediately before IEND, where ancillary chunks belong.
+    pub fn insert_before_iend(&mut self, chunk: Chunk) -> Result<usize> {
+        self.insert_chunk(ChunkPosition::BeforeIend, chunk)
+    }
+
+    /// Inserts a chunk immediately after IHDR.
+    pub fn insert_after_ihdr(&mut self, chunk: Chunk) -> Result<usize> {
+        self.insert_chunk(ChunkPosition::AfterIhdr, chunk)
+    }
+
+    /// Inserts a chunk immediately before the first IDAT chunk.
+    pub fn insert_before_first_idat(&mut self, chunk: Chunk) -> Result<usize> {
+        self.insert_chunk(ChunkPosition::BeforeFirstIdat, chunk)
+    }
+
+    /// Returns the index of the first chunk with the given (known valid) type.
+    fn position_of(&self, chunk_type: &str) -> Result<usize> {
+        self.chunks
+            .iter()
+            .position(|c| c.chunk_type().bytes() == chunk_type.as_bytes())
+            .ok_or_else(|| PngError::ChunkNotFound {
+                chunk_type: chunk_type.to_string(),
+            })
+    }
+
     /// Removes and returns the first chunk with the specified type.
     ///
     /// # Parameters
@@ -130,6 +325,98 @@ impl Png {
             })
     }
 
+    /// Parses a PNG from a stream, reading it one chunk at a time.
+    ///
+    /// Chunks are read up to and including IEND; the rest of the stream
+    /// becomes the [trailer](Png::trailer). Wrap files in a `BufReader`;
+    /// see [`ChunkReader`] to stop before the end.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Png)` - Successfully parsed PNG struct
+    /// * `Err(PngError)` - If reading fails or the stream is not a valid PNG
+    pub fn from_reader(reader: impl Read) -> Result<Self> {
+        let mut reader = ChunkReader::new(reader)?;
+        let chunks = reader.by_ref().collect::<Result<Vec<_>>>()?;
+        let mut trailer = Vec::new();
+        reader.into_inner().read_to_end(&mut trailer)?;
+        Ok(Png { chunks, trailer })
+    }
+
+    /// Parses a PNG from a byte slice with the given options.
+    ///
+    /// Strict options behave like `Png::try_from` and report no diagnostics.
+    /// Lenient options never fail: damaged chunks are kept or skipped and
+    /// each problem is described in the report.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let report = Png::parse_with(&bytes, ParseOptions::lenient())?;
+    /// for diagnostic in &report.diagnostics {
+    ///     println!("{diagnostic}");
+    /// }
+    /// ```
+    pub fn parse_with(bytes: &[u8], options: ParseOptions) -> Result<ParseReport> {
+        if !options.is_lenient() {
+            let png = Png::try_from(bytes)?;
+            return Ok(ParseReport {
+                offsets: png.chunk_offsets(),
+                png,
+                diagnostics: Vec::new(),
+            });
+        }
+        Ok(recovery::parse_lenient(bytes))
+    }
+
+    /// Removes and returns every chunk with the specified type.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Chunk>)` - The removed chunks, in their original order
+    /// * `Err(PngError::InvalidChunkType)` - If the chunk type string is invalid
+    /// * `Err(PngError::ChunkNotFound)` - If no chunk with that type exists
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let removed = png.remove_all_chunks("tEXt")?;
+    /// ```
+    pub fn remove_all_chunks(&mut self, chunk_type: &str) -> Result<Vec<Chunk>> {
+        let c_type =
+            ChunkType::from_str(chunk_type).map_err(|source| PngError::InvalidChunkType {
+                chunk_type: chunk_type.to_string(),
+                source,
+            })?;
+        let (removed, kept) = std::mem::take(&mut self.chunks)
+            .into_iter()
+            .partition(|c| c.chunk_type() == &c_type);
+        self.chunks = kept;
+
+        if removed.is_empty() {
+            return Err(PngError::ChunkNotFound {
+                chunk_type: chunk_type.to_string(),
+            });
+        }
+        Ok(removed)
+    }
+
+    /// Removes and returns the chunk at the given index.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Chunk)` - The removed chunk
+    /// * `Err(PngError::IndexOutOfBounds)` - If `index` is past the end of the chunk list
+    pub fn remove_chunk_at(&mut self, index: usize) -> Result<Chunk> {
+        if index >= self.chunks.len() {
+            return Err(PngError::IndexOutOfBounds {
+                index,
+                len: self.chunks.len(),
+            });
+        }
+        Ok(self.chunks.remove(index))
+    }
+
     /// Returns the standard PNG header bytes.
     ///
     /// This is a constant value that all valid PNG files share.
@@ -168,11 +455,185 @@ impl Png {
         self.chunks.iter().find(|c| c.chunk_type() == &chunk_type)
     }
 
+    /// Iterates over every chunk of the specified type, in file order.
+    ///
+    /// Yields nothing if the chunk type string is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// for chunk in png.chunks_by_type("tEXt") {
+    ///     println!("{}", chunk.length());
+    /// }
+    /// ```
+    pub fn chunks_by_type(&self, chunk_type: &str) -> impl Iterator<Item = &Chunk> {
+        let chunk_type = ChunkType::from_str(chunk_type).ok();
+        self.chunks
+            .iter()
+            .filter(move |c| chunk_type.as_ref() == Some(c.chunk_type()))
+    }
+
+    /// Decodes the IHDR chunk into its typed fields.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Ihdr)` - The image header
+    /// * `Err(PngError::ChunkNotFound)` - If there is no IHDR chunk
+    /// * `Err(PngError::InvalidIhdr)` - If the IHDR chunk is malformed
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let ihdr = png.header_info()?;
+    /// println!("{}x{}", ihdr.width(), ihdr.height());
+    /// ```
+    pub fn header_info(&self) -> Result<Ihdr> {
+        let index = self.position_of("IHDR")?;
+        Ok(Ihdr::try_from(&self.chunks[index])?)
+    }
+
+    /// Concatenates the data of every IDAT chunk, in order.
+    ///
+    /// The result is the compressed zlib stream holding the image pixels.
+    pub fn compressed_image_data(&self) -> Vec<u8> {
+        self.chunks_by_type("IDAT")
+            .flat_map(|chunk| chunk.data())
+            .copied()
+            .collect()
+    }
+
+    /// Decodes the image pixels into unfiltered scanlines.
+    ///
+    /// Joins the IDAT chunks, inflates the stream and reverses the per-row
+    /// filters using the geometry from IHDR.
+    ///
+    /// # Errors
+    ///
+    /// * `Err(PngError::ChunkNotFound)` / `Err(PngError::InvalidIhdr)` - If IHDR is missing or invalid
+    /// * `Err(PngError::InvalidImageData)` - If there is no IDAT, the stream is
+    ///   corrupt or truncated, or a scanline has an unknown filter type
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let image = png.image_data()?;
+    /// println!("{} rows of {} bytes", image.rows().len(), image.row_bytes());
+    /// ```
+    pub fn image_data(&self) -> Result<ImageData> {
+        let header = self.header_info()?;
+        if self.chunks_by_type("IDAT").next().is_none() {
+            return Err(ImageDataError::NoImageData.into());
+        }
+        Ok(ImageData::decode(&header, &self.compressed_image_data())?)
+    }
+
+    /// Replaces the image pixels, re-encoding them into new IDAT chunks.
+    ///
+    /// The scanlines are filtered and deflated as `options` asks, then split
+    /// into IDAT chunks of at most `options.max_chunk_size()` bytes. These take
+    /// the place of the old IDAT chunks (or go before IEND if there were none),
+    /// and IHDR is rewritten from `image.header()` so the two always agree.
+    /// Setting an interlace method in `options` converts the file to it.
+    ///
+    /// # Errors
+    ///
+    /// * `Err(PngError::ChunkNotFound)` - If there is no IHDR chunk
+    /// * `Err(PngError::ChunkNotFound)` - If there is neither an IDAT nor an IEND chunk
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut image = png.image_data()?;
+    /// image.pixels_mut()[0] ^= 1;
+    /// png.set_image_data(&image, &ImageEncodeOptions::default())?;
+    /// ```
+    pub fn set_image_data(
+        &mut self,
+        image: &ImageData,
+        options: &ImageEncodeOptions,
+    ) -> Result<()> {
+        let ihdr_index = self.position_of("IHDR")?;
+        let index = match self.position_of("IDAT") {
+            Ok(index) => index,
+            Err(_) => self.position_of("IEND")?,
+        };
+
+        let compressed = image.encode(options);
+        let idat_type = ChunkType::from_str("IDAT").expect("IDAT is a valid chunk type");
+        let idats = compressed
+            .chunks(options.max_chunk_size())
+            .map(|data| Chunk::new(idat_type, data.to_vec()));
+
+        self.chunks[ihdr_index] = options.header_for(image.header()).to_chunk();
+        self.chunks.retain(|chunk| chunk.chunk_type() != &idat_type);
+        self.chunks.splice(index..index, idats);
+        Ok(())
+    }
+
+    /// Checks the chunk layout against the PNG specification.
+    ///
+    /// Verifies that IHDR is first, IEND is last, IDAT chunks are present and
+    /// consecutive, PLTE precedes IDAT, and unique chunks appear only once.
+    ///
+    /// # Returns
+    ///
+    /// Every violation found, each carrying the offending chunk index where
+    /// applicable. An empty vector means the layout is valid.
+    pub fn validate(&self) -> Vec<Violation> {
+        validation::validate_chunks(&self.chunks)
+    }
+
+    /// Lists every chunk whose stored CRC is wrong, without changing anything.
+    ///
+    /// Only chunks from lenient parsing can have a wrong CRC.
+    pub fn bad_crcs(&self) -> Vec<CrcFix> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| !chunk.has_valid_crc())
+            .map(|(index, chunk)| CrcFix {
+                index,
+                chunk_type: *chunk.chunk_type(),
+                old: chunk.crc(),
+                new: Chunk::calculate_crc(chunk.chunk_type(), chunk.data()),
+            })
+            .collect()
+    }
+
+    /// Rewrites every wrong CRC with the one computed from the chunk's contents.
+    ///
+    /// # Returns
+    ///
+    /// One entry per chunk fixed, with its old and new CRC. Empty if every CRC
+    /// was already correct.
+    pub fn recompute_crcs(&mut self) -> Vec<CrcFix> {
+        let fixes = self.bad_crcs();
+        for fix in &fixes {
+            self.chunks[fix.index].recompute_crc();
+        }
+        fixes
+    }
+
+    /// Writes this PNG to a stream, one chunk at a time.
+    ///
+    /// Unlike [`Png::as_bytes`], this never holds more than one chunk's
+    /// worth of output. The stream is flushed before returning.
+    pub fn write_to(&self, writer: impl Write) -> Result<()> {
+        let mut writer = PngWriter::new(writer)?;
+        for chunk in &self.chunks {
+            writer.write_chunk(chunk)?;
+        }
+        writer.write_trailer(&self.trailer)?;
+        writer.finish()?;
+        Ok(())
+    }
+
     /// Serializes this PNG to its byte representation.
     ///
     /// This produces a valid PNG file format including:
     /// 1. The 8-byte PNG signature header
     /// 2. All chunks in order, each with its length, type, data, and CRC
+    /// 3. The trailer, if any
     ///
     /// # Returns
     ///
@@ -182,13 +643,15 @@ impl Png {
             .iter()
             .copied()
             .chain(self.chunks.iter().flat_map(|c| c.as_bytes().into_iter()))
+            .chain(self.trailer.iter().copied())
             .collect()
     }
 }
 
 /// Implementation for converting a byte slice into a `Png`.
 ///
-/// This parses a raw PNG file from its byte representation.
+/// This parses a raw PNG file from its byte representation, borrowing it
+/// as a [`PngRef`] first and then copying the chunks.
 impl TryFrom<&[u8]> for Png {
     type Error = PngError;
 
@@ -203,64 +666,7 @@ impl TryFrom<&[u8]> for Png {
     /// * `Ok(Png)` - Successfully parsed PNG struct
     /// * `Err(PngError)` - Various parsing errors (invalid header, insufficient data, invalid chunks, etc.)
     fn try_from(bytes: &[u8]) -> Result<Self> {
-        // Validate PNG signature header
-        let header = bytes.get(..8).ok_or_else(|| PngError::NotEnoughBytes {
-            position: 0,
-            required: Self::STANDARD_HEADER.len(),
-            actual: bytes.len(),
-        })?;
-
-        if header != &Self::STANDARD_HEADER {
-            return Err(PngError::HeaderMismatch {
-                expected: Self::STANDARD_HEADER,
-                actual: header.try_into().expect("header slice is exactly 8 bytes"),
-            });
-        }
-
-        // Parse chunks iteratively
-        let mut chunks = Vec::new();
-        let mut offset: usize = Self::STANDARD_HEADER.len();
-
-        while offset < bytes.len() {
-            // Extract the 4-byte length field
-            let length_bytes =
-                bytes
-                    .get(offset..offset + 4)
-                    .ok_or_else(|| PngError::NotEnoughBytes {
-                        position: offset,
-                        required: Chunk::LENGTH_SIZE,
-                        actual: bytes.len().saturating_sub(offset),
-                    })?;
-
-            let data_length = u32::from_be_bytes(
-                length_bytes
-                    .try_into()
-                    .expect("length field is exactly 4 bytes"),
-            ) as usize;
-
-            // Calculate total chunk length: length field + type field + data + CRC
-            let chunk_length =
-                data_length + Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE + Chunk::CRC_SIZE;
-
-            // Extract the complete chunk data
-            let chunk_bytes = bytes.get(offset..offset + chunk_length).ok_or_else(|| {
-                PngError::NotEnoughBytes {
-                    position: offset,
-                    required: chunk_length,
-                    actual: bytes.len().saturating_sub(offset),
-                }
-            })?;
-
-            let chunk = Chunk::try_from(chunk_bytes).map_err(|source| PngError::InvalidChunk {
-                position: offset,
-                source,
-            })?;
-
-            chunks.push(chunk);
-            offset += chunk_length;
-        }
-
-        Ok(Png::from_chunks(chunks))
+        PngRef::try_from(bytes).map(Png::from)
     }
 }
 
@@ -303,6 +709,7 @@ mod tests {
     use super::*;
     use crate::chunk::Chunk;
     use crate::chunk_type::ChunkType;
+    use crate::ihdr::InterlaceMethod;
     use std::convert::TryFrom;
     use std::error::Error;
 
@@ -424,6 +831,291 @@ mod tests {
         assert_eq!(&chunk.data_as_str().unwrap().to_string(), "Message");
     }
 
+    #[test]
+    fn test_chunks_by_type() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("FrSt", "I am a second first chunk").unwrap());
+        let texts: Vec<&str> = png
+            .chunks_by_type("FrSt")
+            .map(|c| c.data_as_str().unwrap())
+            .collect();
+        assert_eq!(texts, ["I am the first chunk", "I am a second first chunk"]);
+        assert_eq!(png.chunks_by_type("NoNe").count(), 0);
+        assert_eq!(png.chunks_by_type("bad!").count(), 0);
+    }
+
+    #[test]
+    fn test_remove_all_chunks() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("FrSt", "Another").unwrap());
+        let removed = png.remove_all_chunks("FrSt").unwrap();
+        assert_eq!(removed.len(), 2);
+        assert_eq!(png.chunks().len(), 2);
+        assert!(png.chunk_by_type("FrSt").is_none());
+        assert!(matches!(
+            png.remove_all_chunks("FrSt"),
+            Err(PngError::ChunkNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_remove_chunk_at() {
+        let mut png = testing_png();
+        let removed = png.remove_chunk_at(1).unwrap();
+        assert_eq!(&removed.chunk_type().to_string(), "miDl");
+        assert_eq!(png.chunks().len(), 2);
+        assert!(matches!(
+            png.remove_chunk_at(2),
+            Err(PngError::IndexOutOfBounds { index: 2, len: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_insert_before_iend() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let len = png.chunks().len();
+        let index = png
+            .insert_before_iend(chunk_from_strings("TeSt", "Message").unwrap())
+            .unwrap();
+        assert_eq!(index, len - 1);
+        assert_eq!(&png.chunks()[index].chunk_type().to_string(), "TeSt");
+        assert_eq!(&png.chunks()[len].chunk_type().to_string(), "IEND");
+    }
+
+    #[test]
+    fn test_insert_after_ihdr() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let index = png
+            .insert_after_ihdr(chunk_from_strings("TeSt", "Message").unwrap())
+            .unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(&png.chunks()[0].chunk_type().to_string(), "IHDR");
+        assert_eq!(&png.chunks()[1].chunk_type().to_string(), "TeSt");
+    }
+
+    #[test]
+    fn test_insert_before_first_idat() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let index = png
+            .insert_before_first_idat(chunk_from_strings("TeSt", "Message").unwrap())
+            .unwrap();
+        assert_eq!(&png.chunks()[index].chunk_type().to_string(), "TeSt");
+        assert_eq!(&png.chunks()[index + 1].chunk_type().to_string(), "IDAT");
+    }
+
+    #[test]
+    fn test_insert_chunk_at() {
+        let mut png = testing_png();
+        png.insert_chunk_at(1, chunk_from_strings("TeSt", "Message").unwrap())
+            .unwrap();
+        assert_eq!(&png.chunks()[1].chunk_type().to_string(), "TeSt");
+
+        let result = png.insert_chunk_at(10, chunk_from_strings("TeSt", "Message").unwrap());
+        assert!(matches!(
+            result,
+            Err(PngError::IndexOutOfBounds { index: 10, len: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_insert_missing_anchor() {
+        let mut png = testing_png();
+        let result = png.insert_before_iend(chunk_from_strings("TeSt", "Message").unwrap());
+        assert!(matches!(result, Err(PngError::ChunkNotFound { .. })));
+    }
+
+    #[test]
+    fn test_header_info() {
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let ihdr = png.header_info().unwrap();
+        assert_eq!(ihdr.width(), 50);
+        assert_eq!(ihdr.height(), 50);
+        assert_eq!(ihdr.bit_depth(), 8);
+        assert_eq!(ihdr.color_type(), crate::ihdr::ColorType::Rgba);
+
+        let png = testing_png();
+        assert!(matches!(
+            png.header_info(),
+            Err(PngError::ChunkNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_image_data() {
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let image = png.image_data().unwrap();
+        assert_eq!(image.rows().len(), 50);
+        assert_eq!(image.row_bytes(), 200);
+
+        let header_only = Png::from_chunks(vec![png.header_info().unwrap().to_chunk()]);
+        assert!(matches!(
+            header_only.image_data(),
+            Err(PngError::InvalidImageData(ImageDataError::NoImageData))
+        ));
+    }
+
+    #[test]
+    fn test_set_image_data() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let mut image = png.image_data().unwrap();
+        image.pixels_mut()[0] ^= 0xff;
+
+        let options = ImageEncodeOptions::default().with_max_chunk_size(1000);
+        png.set_image_data(&image, &options).unwrap();
+        let png = Png::try_from(&png.as_bytes()[..]).unwrap();
+        assert_eq!(png.image_data().unwrap(), image);
+
+        let types: Vec<String> = png
+            .chunks()
+            .iter()
+            .map(|c| c.chunk_type().to_string())
+            .collect();
+        let idats = types.iter().filter(|t| *t == "IDAT").count();
+        assert!(idats > 1);
+        assert!(png.chunks_by_type("IDAT").all(|c| c.length() <= 1000));
+        assert_eq!(types.last().map(String::as_str), Some("IEND"));
+        assert!(png.validate().is_empty());
+    }
+
+    #[test]
+    fn test_convert_interlace_on_save() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let image = png.image_data().unwrap();
+
+        let interlaced = ImageEncodeOptions::default().with_interlace(InterlaceMethod::Adam7);
+        png.set_image_data(&image, &interlaced).unwrap();
+        let header = png.header_info().unwrap();
+        assert_eq!(header.interlace_method(), InterlaceMethod::Adam7);
+        let decoded = png.image_data().unwrap();
+        assert_eq!(decoded.pixels(), image.pixels());
+
+        let plain = ImageEncodeOptions::default().with_interlace(InterlaceMethod::None);
+        png.set_image_data(&decoded, &plain).unwrap();
+        assert_eq!(png.image_data().unwrap(), image);
+    }
+
+    #[test]
+    fn test_trailer_round_trip() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("IEND", "").unwrap());
+        png.set_trailer(b"PK\x05\x06 appended archive".to_vec());
+
+        let bytes = png.as_bytes();
+        let parsed = Png::try_from(&bytes[..]).unwrap();
+        assert_eq!(parsed.chunks().len(), 4);
+        assert_eq!(parsed.trailer(), b"PK\x05\x06 appended archive");
+
+        let mut written = Vec::new();
+        parsed.write_to(&mut written).unwrap();
+        assert_eq!(written, bytes);
+    }
+
+    #[test]
+    fn test_chunk_offsets() {
+        let png = testing_png();
+        assert_eq!(png.chunk_offsets(), [8, 40, 70]);
+        assert!(Png::from_chunks(Vec::new()).chunk_offsets().is_empty());
+    }
+
+    #[test]
+    fn test_take_trailer() {
+        let mut png = testing_png();
+        png.set_trailer(b"junk".to_vec());
+        assert_eq!(png.take_trailer(), b"junk");
+        assert!(png.trailer().is_empty());
+    }
+
+    #[test]
+    fn test_insert_after_iend_refused() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let len = png.chunks().len();
+        let late = || chunk_from_strings("LatE", "after the end").unwrap();
+        assert!(matches!(
+            png.insert_chunk(ChunkPosition::End, late()),
+            Err(PngError::AfterIend { index }) if index == len
+        ));
+        assert!(matches!(
+            png.insert_chunk(ChunkPosition::Index(len), late()),
+            Err(PngError::AfterIend { .. })
+        ));
+        assert_eq!(png.chunks().len(), len);
+
+        let mut unfinished = testing_png();
+        assert_eq!(
+            unfinished.insert_chunk(ChunkPosition::End, late()).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_chunks_after_iend_become_trailer() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("IEND", "").unwrap());
+        let late = chunk_from_strings("LatE", "after the end").unwrap();
+        let late_bytes = late.as_bytes();
+        png.append_chunk(late);
+
+        let parsed = Png::try_from(&png.as_bytes()[..]).unwrap();
+        assert_eq!(parsed.chunks().len(), 4);
+        assert_eq!(parsed.trailer(), &late_bytes[..]);
+    }
+
+    #[test]
+    fn test_recompute_crcs() {
+        let mut bytes = testing_png().as_bytes();
+        bytes[20] ^= 1;
+        let mut png = Png::parse_with(&bytes, ParseOptions::lenient())
+            .unwrap()
+            .png;
+
+        let expected = [CrcFix {
+            index: 0,
+            chunk_type: ChunkType::from_str("FrSt").unwrap(),
+            old: png.chunks()[0].crc(),
+            new: Chunk::calculate_crc(png.chunks()[0].chunk_type(), png.chunks()[0].data()),
+        }];
+        assert_eq!(png.bad_crcs(), expected);
+        assert_eq!(png.recompute_crcs(), expected);
+        assert!(png.bad_crcs().is_empty());
+        assert!(png.recompute_crcs().is_empty());
+        assert!(Png::try_from(&png.as_bytes()[..]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_image_file() {
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        assert!(png.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_missing_critical_chunks() {
+        let png = testing_png();
+        let violations = png.validate();
+        assert!(violations.contains(&Violation::MissingIhdr));
+        assert!(violations.contains(&Violation::MissingIdat));
+        assert!(violations.contains(&Violation::MissingIend));
+    }
+
+    #[test]
+    fn test_validate_misordered_chunks() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let ihdr = png.remove_first_chunk("IHDR").unwrap();
+        png.append_chunk(ihdr);
+        png.append_chunk(chunk_from_strings("IDAT", "").unwrap());
+        png.append_chunk(chunk_from_strings("PLTE", "").unwrap());
+        png.append_chunk(chunk_from_strings("gAMA", "").unwrap());
+
+        let violations = png.validate();
+        assert!(violations.contains(&Violation::IhdrNotFirst { index: 6 }));
+        assert!(violations.contains(&Violation::NonContiguousIdat { index: 7 }));
+        assert!(violations.contains(&Violation::PlteAfterIdat { index: 8 }));
+        assert!(violations.contains(&Violation::IendNotLast { index: 5 }));
+        assert!(violations.contains(&Violation::DuplicateChunk {
+            chunk_type: "gAMA",
+            index: 9
+        }));
+    }
+
     #[test]
     fn test_remove_first_chunk() {
         let mut png = testing_png();
@@ -1,4 +1,5 @@
-use std::fs;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 
 use pngme::{Png, PngError};
@@ -21,13 +22,12 @@ pub enum PngFileError {
 impl PngFile {
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref().to_path_buf();
-        let bytes = fs::read(&path)?;
-        let png = Png::try_from(&bytes[..])?;
+        let png = Png::from_reader(BufReader::new(File::open(&path)?))?;
         Ok(Self { path, png })
     }
 
     pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
-        fs::write(path, &self.png.as_bytes())?;
+        self.png.write_to(BufWriter::new(File::create(path)?))?;
         Ok(())
     }
 
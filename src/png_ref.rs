@@ -0,0 +1,211 @@
+use std::str::FromStr;
+
+use crate::{
+    chunk::{Chunk, ChunkError},
+    chunk_ref::ChunkRef,
+    chunk_type::ChunkType,
+    png::{Png, PngError, Result},
+};
+
+/// A PNG whose chunks borrow from the buffer it was parsed from.
+///
+/// This is the read-only counterpart of [`Png`]: parsing checks the same
+/// things, but chunk data is never copied. It suits scanning large images
+/// held in memory or memory-mapped. Convert it with [`PngRef::to_png`] to
+/// insert or remove chunks.
+///
+/// # Examples
+///
+/// ```ignore
+/// let bytes = fs::read("image.png")?;
+/// let png = PngRef::try_from(&bytes[..])?;
+/// for chunk in png.chunks_by_type("tEXt") {
+///     println!("{}", chunk.length());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PngRef<'a> {
+    chunks: Vec<ChunkRef<'a>>,
+    trailer: &'a [u8],
+}
+
+impl<'a> PngRef<'a> {
+    /// Returns all chunks in file order.
+    pub fn chunks(&self) -> &[ChunkRef<'a>] {
+        &self.chunks
+    }
+
+    /// Returns the bytes that followed the IEND chunk, if any.
+    pub fn trailer(&self) -> &'a [u8] {
+        self.trailer
+    }
+
+    /// Finds the first chunk with the specified type.
+    ///
+    /// Returns `None` if no chunk matches or the chunk type string is invalid.
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&ChunkRef<'a>> {
+        self.chunks_by_type(chunk_type).next()
+    }
+
+    /// Iterates over every chunk of the specified type, in file order.
+    ///
+    /// Yields nothing if the chunk type string is invalid.
+    pub fn chunks_by_type(&self, chunk_type: &str) -> impl Iterator<Item = &ChunkRef<'a>> {
+        let chunk_type = ChunkType::from_str(chunk_type).ok();
+        self.chunks
+            .iter()
+            .filter(move |c| chunk_type.as_ref() == Some(c.chunk_type()))
+    }
+
+    /// Copies every chunk into an owned, editable [`Png`].
+    pub fn to_png(&self) -> Png {
+        let mut png = Png::from_chunks(self.chunks.iter().map(ChunkRef::to_chunk).collect());
+        png.set_trailer(self.trailer.to_vec());
+        png
+    }
+}
+
+impl From<PngRef<'_>> for Png {
+    fn from(png: PngRef<'_>) -> Self {
+        png.to_png()
+    }
+}
+
+/// Parses a PNG from a byte slice without copying any chunk data.
+///
+/// This is the parser behind `Png::try_from` as well, which copies the
+/// result.
+impl<'a> TryFrom<&'a [u8]> for PngRef<'a> {
+    type Error = PngError;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self> {
+        // Validate PNG signature header
+        let header_len = Png::STANDARD_HEADER.len();
+        let header = bytes.get(..header_len).ok_or(PngError::NotEnoughBytes {
+            position: 0,
+            required: header_len,
+            actual: bytes.len(),
+        })?;
+        if header != Png::STANDARD_HEADER {
+            return Err(PngError::HeaderMismatch {
+                expected: Png::STANDARD_HEADER,
+                actual: header.try_into().expect("header slice is exactly 8 bytes"),
+            });
+        }
+
+        // Borrow chunks one after another, up to IEND
+        let mut chunks = Vec::new();
+        let mut offset = header_len;
+        while offset < bytes.len() {
+            if chunks
+                .last()
+                .is_some_and(|c: &ChunkRef| c.chunk_type().bytes() == *b"IEND")
+            {
+                break;
+            }
+
+            let remaining = &bytes[offset..];
+            let length_bytes: [u8; 4] = remaining
+                .get(..Chunk::LENGTH_SIZE)
+                .and_then(|slice| slice.try_into().ok())
+                .ok_or(PngError::NotEnoughBytes {
+                    position: offset,
+                    required: Chunk::LENGTH_SIZE,
+                    actual: remaining.len(),
+                })?;
+            let data_length = u32::from_be_bytes(length_bytes) as usize;
+            if data_length > Chunk::MAX_DATA_SIZE {
+                return Err(PngError::InvalidChunk {
+                    position: offset,
+                    source: ChunkError::TooLarge { size: data_length },
+                });
+            }
+
+            let chunk_length =
+                data_length + Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE + Chunk::CRC_SIZE;
+            let chunk_bytes = remaining
+                .get(..chunk_length)
+                .ok_or(PngError::NotEnoughBytes {
+                    position: offset,
+                    required: chunk_length,
+                    actual: remaining.len(),
+                })?;
+            let chunk =
+                ChunkRef::try_from(chunk_bytes).map_err(|source| PngError::InvalidChunk {
+                    position: offset,
+                    source,
+                })?;
+
+            chunks.push(chunk);
+            offset += chunk_length;
+        }
+
+        Ok(Self {
+            chunks,
+            trailer: &bytes[offset..],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn testing_bytes() -> Vec<u8> {
+        Png::from_chunks(vec![
+            Chunk::new(ChunkType::from_str("FrSt").unwrap(), b"first".to_vec()),
+            Chunk::new(ChunkType::from_str("miDl").unwrap(), b"middle".to_vec()),
+            Chunk::new(ChunkType::from_str("FrSt").unwrap(), b"again".to_vec()),
+        ])
+        .as_bytes()
+    }
+
+    #[test]
+    fn test_parses_without_copying() {
+        let bytes = testing_bytes();
+        let png = PngRef::try_from(&bytes[..]).unwrap();
+        assert_eq!(png.chunks().len(), 3);
+        assert_eq!(png.chunks()[0].data().as_ptr(), bytes[16..].as_ptr());
+        assert_eq!(png.chunk_by_type("miDl").unwrap().data(), b"middle");
+        assert_eq!(png.chunks_by_type("FrSt").count(), 2);
+        assert!(png.chunk_by_type("NoNe").is_none());
+    }
+
+    #[test]
+    fn test_to_png_round_trips() {
+        let bytes = testing_bytes();
+        let png = PngRef::try_from(&bytes[..]).unwrap().to_png();
+        assert_eq!(png.as_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_trailer_is_borrowed() {
+        let mut bytes = testing_bytes();
+        bytes.extend_from_slice(
+            &Chunk::new(ChunkType::from_str("IEND").unwrap(), vec![]).as_bytes(),
+        );
+        bytes.extend_from_slice(b"appended");
+        let png = PngRef::try_from(&bytes[..]).unwrap();
+        assert_eq!(png.chunks().len(), 4);
+        assert_eq!(png.trailer(), b"appended");
+        assert_eq!(png.to_png().as_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_errors_match_stream_parse() {
+        let bytes = testing_bytes();
+        let truncated = &bytes[..bytes.len() - 3];
+        assert!(matches!(
+            PngRef::try_from(truncated),
+            Err(PngError::NotEnoughBytes { position: 43, .. })
+        ));
+        assert!(matches!(
+            Png::from_reader(truncated),
+            Err(PngError::NotEnoughBytes { position: 43, .. })
+        ));
+        assert!(matches!(
+            PngRef::try_from(&bytes[1..]),
+            Err(PngError::HeaderMismatch { .. })
+        ));
+    }
+}
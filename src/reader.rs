@@ -0,0 +1,287 @@
+use std::io::{self, Read};
+
+use crate::{
+    chunk::{Chunk, ChunkError},
+    chunk_type::ChunkType,
+    png::{Png, PngError, Result},
+};
+
+/// Reads the chunks of a PNG stream one at a time.
+///
+/// The signature is checked when the reader is created; after that each call
+/// to `next` reads exactly one chunk, so callers can stop as soon as they have
+/// what they need without reading (or holding) the rest of the file.
+/// Iteration ends after the IEND chunk or at the end of the stream, and
+/// stops after the first error. Any bytes after IEND are left unread; get
+/// them from [`ChunkReader::into_inner`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let file = BufReader::new(File::open("image.png")?);
+/// let mut reader = ChunkReader::new(file)?;
+/// while let Some(chunk) = reader.next() {
+///     let offset = reader.chunk_offset();
+///     println!("{} at byte {offset}", chunk?.chunk_type());
+/// }
+/// ```
+pub struct ChunkReader<R> {
+    reader: R,
+    /// Bytes consumed from the stream so far
+    offset: usize,
+    /// Byte offset of the chunk most recently returned
+    chunk_offset: usize,
+    done: bool,
+}
+
+impl<R: Read> ChunkReader<R> {
+    /// Wraps a stream positioned at the start of a PNG file and checks its signature.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ChunkReader)` - Ready to read the first chunk
+    /// * `Err(PngError::NotEnoughBytes)` - If the stream ends inside the signature
+    /// * `Err(PngError::HeaderMismatch)` - If the signature is not the PNG signature
+    /// * `Err(PngError::Io)` - If reading fails
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut header = [0; Png::STANDARD_HEADER.len()];
+        let read = read_full(&mut reader, &mut header)?;
+        if read < header.len() {
+            return Err(PngError::NotEnoughBytes {
+                position: 0,
+                required: header.len(),
+                actual: read,
+            });
+        }
+        if header != Png::STANDARD_HEADER {
+            return Err(PngError::HeaderMismatch {
+                expected: Png::STANDARD_HEADER,
+                actual: header,
+            });
+        }
+
+        Ok(Self {
+            reader,
+            offset: header.len(),
+            chunk_offset: header.len(),
+            done: false,
+        })
+    }
+
+    /// Number of bytes consumed from the stream, including the signature.
+    ///
+    /// Between chunks this is the offset at which the next chunk starts.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Byte offset at which the most recently returned chunk starts.
+    pub fn chunk_offset(&self) -> usize {
+        self.chunk_offset
+    }
+
+    /// Returns the underlying stream, positioned after the last chunk read.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads one chunk, or `None` at a clean end of stream.
+    fn read_chunk(&mut self) -> Result<Option<Chunk>> {
+        let position = self.offset;
+
+        // Length and type fields
+        let mut head = [0; Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE];
+        let read = read_full(&mut self.reader, &mut head)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if read < Chunk::LENGTH_SIZE {
+            return Err(PngError::NotEnoughBytes {
+                position,
+                required: Chunk::LENGTH_SIZE,
+                actual: read,
+            });
+        }
+        let (length_bytes, type_bytes) = head.split_at(Chunk::LENGTH_SIZE);
+        let data_length = u32::from_be_bytes(
+            length_bytes
+                .try_into()
+                .expect("length field is exactly 4 bytes"),
+        ) as usize;
+        let chunk_length = data_length + Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE + Chunk::CRC_SIZE;
+        let invalid = |source| PngError::InvalidChunk { position, source };
+
+        if data_length > Chunk::MAX_DATA_SIZE {
+            return Err(invalid(ChunkError::TooLarge { size: data_length }));
+        }
+
+        // Data is read through `take` so a corrupt length cannot force a huge allocation
+        let mut data = Vec::new();
+        let data_read = if read == head.len() {
+            (&mut self.reader)
+                .take(data_length as u64)
+                .read_to_end(&mut data)?
+        } else {
+            0
+        };
+        let mut crc_bytes = [0; Chunk::CRC_SIZE];
+        let crc_read = if data_read == data_length {
+            read_full(&mut self.reader, &mut crc_bytes)?
+        } else {
+            0
+        };
+
+        let actual = read + data_read + crc_read;
+        if actual < chunk_length {
+            return Err(PngError::NotEnoughBytes {
+                position,
+                required: chunk_length,
+                actual,
+            });
+        }
+
+        let chunk_type = ChunkType::try_from(
+            <[u8; 4]>::try_from(type_bytes).expect("type field is exactly 4 bytes"),
+        )
+        .map_err(|e| invalid(ChunkError::InvalidChunkType(e)))?;
+        let chunk =
+            Chunk::from_parts(chunk_type, data, u32::from_be_bytes(crc_bytes)).map_err(invalid)?;
+
+        self.chunk_offset = position;
+        self.offset += chunk_length;
+        Ok(Some(chunk))
+    }
+}
+
+impl<R: Read> Iterator for ChunkReader<R> {
+    type Item = Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.read_chunk().transpose();
+        self.done = match &result {
+            Some(Ok(chunk)) => chunk.chunk_type().bytes() == *b"IEND",
+            _ => true,
+        };
+        result
+    }
+}
+
+/// Fills `buf` from `reader`, returning fewer bytes only at end of stream.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn testing_bytes() -> Vec<u8> {
+        Png::from_chunks(vec![
+            Chunk::new(ChunkType::from_str("FrSt").unwrap(), b"first".to_vec()),
+            Chunk::new(ChunkType::from_str("miDl").unwrap(), Vec::new()),
+            Chunk::new(ChunkType::from_str("LASt").unwrap(), b"last".to_vec()),
+        ])
+        .as_bytes()
+    }
+
+    #[test]
+    fn test_reads_chunks_with_offsets() {
+        let bytes = testing_bytes();
+        let mut reader = ChunkReader::new(&bytes[..]).unwrap();
+        assert_eq!(reader.offset(), 8);
+
+        let mut seen = Vec::new();
+        while let Some(chunk) = reader.next() {
+            seen.push((
+                chunk.unwrap().chunk_type().to_string(),
+                reader.chunk_offset(),
+            ));
+        }
+        assert_eq!(
+            seen,
+            [
+                ("FrSt".to_string(), 8),
+                ("miDl".to_string(), 25),
+                ("LASt".to_string(), 37)
+            ]
+        );
+        assert_eq!(reader.offset(), bytes.len());
+    }
+
+    #[test]
+    fn test_stops_early_without_reading_rest() {
+        let bytes = testing_bytes();
+        let mut reader = ChunkReader::new(&bytes[..]).unwrap();
+        reader.next().unwrap().unwrap();
+        assert_eq!(reader.into_inner().len(), bytes.len() - 25);
+    }
+
+    #[test]
+    fn test_stops_at_iend() {
+        let mut bytes = Png::from_chunks(vec![
+            Chunk::new(ChunkType::from_str("FrSt").unwrap(), b"first".to_vec()),
+            Chunk::new(ChunkType::from_str("IEND").unwrap(), Vec::new()),
+        ])
+        .as_bytes();
+        bytes.extend_from_slice(b"PK\x03\x04 not a chunk");
+
+        let mut reader = ChunkReader::new(&bytes[..]).unwrap();
+        assert_eq!(reader.by_ref().count(), 2);
+        assert_eq!(reader.into_inner(), b"PK\x03\x04 not a chunk");
+    }
+
+    #[test]
+    fn test_invalid_signature() {
+        let bytes = [0u8; 16];
+        assert!(matches!(
+            ChunkReader::new(&bytes[..]),
+            Err(PngError::HeaderMismatch { .. })
+        ));
+        assert!(matches!(
+            ChunkReader::new(&bytes[..3]),
+            Err(PngError::NotEnoughBytes { actual: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_truncated_chunk() {
+        let bytes = testing_bytes();
+        let mut reader = ChunkReader::new(&bytes[..bytes.len() - 2]).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(PngError::NotEnoughBytes {
+                position: 37,
+                required: 16,
+                actual: 14
+            }))
+        ));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_crc_mismatch() {
+        let mut bytes = testing_bytes();
+        bytes[16] ^= 0xff;
+        let mut reader = ChunkReader::new(&bytes[..]).unwrap();
+        assert!(matches!(
+            reader.next(),
+            Some(Err(PngError::InvalidChunk { position: 8, .. }))
+        ));
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,348 @@
+use std::fmt;
+
+use crate::{chunk::Chunk, chunk_type::ChunkType, png::Png};
+
+/// Controls how strictly [`Png::parse_with`] treats damaged input.
+///
+/// The default is strict: the first problem aborts the parse, exactly like
+/// `Png::try_from`. Lenient parsing never fails; it keeps whatever chunks it
+/// can and describes everything it had to work around in [`Diagnostic`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    lenient: bool,
+}
+
+impl ParseOptions {
+    /// Options that reject any malformed input.
+    pub fn strict() -> Self {
+        Self { lenient: false }
+    }
+
+    /// Options that recover as much as possible from damaged input.
+    pub fn lenient() -> Self {
+        Self { lenient: true }
+    }
+
+    /// True if parsing recovers from errors instead of failing.
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+}
+
+/// The chunks recovered by [`Png::parse_with`] and the problems found on the way.
+pub struct ParseReport {
+    pub png: Png,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Byte offset in the input of each chunk, in chunk order
+    ///
+    /// Unlike [`Png::chunk_offsets`], these account for skipped bytes and a
+    /// missing signature.
+    pub offsets: Vec<usize>,
+}
+
+/// A problem that lenient parsing worked around.
+///
+/// Indices refer to positions in the recovered `Png::chunks()`; offsets are
+/// byte positions in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// The input does not start with the PNG signature.
+    BadSignature,
+
+    /// A chunk's stored CRC does not match its contents.
+    ///
+    /// The chunk is kept with its stored CRC; `Chunk::has_valid_crc` reports it.
+    CrcMismatch {
+        index: usize,
+        offset: usize,
+        chunk_type: ChunkType,
+        expected: u32,
+        actual: u32,
+    },
+
+    /// Bytes that do not form a chunk were skipped to reach the next chunk header.
+    Skipped { offset: usize, length: usize },
+
+    /// The input ends inside a chunk; the bytes that were present are kept.
+    Truncated {
+        index: usize,
+        offset: usize,
+        chunk_type: ChunkType,
+        required: usize,
+        actual: usize,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::BadSignature => write!(f, "PNG signature is missing or damaged"),
+            Diagnostic::CrcMismatch {
+                index,
+                offset,
+                chunk_type,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Chunk {index} ({chunk_type} at byte {offset}): CRC mismatch, \
+                 expected 0x{expected:08X}, found 0x{actual:08X}"
+            ),
+            Diagnostic::Skipped { offset, length } => {
+                write!(f, "Skipped {length} unreadable byte(s) at byte {offset}")
+            }
+            Diagnostic::Truncated {
+                index,
+                offset,
+                chunk_type,
+                required,
+                actual,
+            } => write!(
+                f,
+                "Chunk {index} ({chunk_type} at byte {offset}): truncated, \
+                 {actual} of {required} bytes present"
+            ),
+        }
+    }
+}
+
+/// Parses chunks from `bytes`, skipping damage instead of failing.
+///
+/// A chunk is accepted when its header is plausible (a letter-only type and
+/// a length that fits the spec limit) and either its CRC matches or the next
+/// chunk header lines up where it should. Anything else is treated as damage
+/// and skipped up to the next header whose chunk has a valid CRC.
+/// Parsing stops after IEND; the bytes after it are returned as the trailer.
+pub(crate) fn parse_lenient(bytes: &[u8]) -> ParseReport {
+    let mut chunks = Vec::new();
+    let mut offsets = Vec::new();
+    let mut trailer = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    let mut offset = if bytes.starts_with(&Png::STANDARD_HEADER) {
+        Png::STANDARD_HEADER.len()
+    } else {
+        diagnostics.push(Diagnostic::BadSignature);
+        0
+    };
+
+    while offset < bytes.len() {
+        // Where the next valid chunk starts, if already searched for
+        let mut resynced = None;
+        if let Some((chunk_type, data_length)) = header_at(bytes, offset) {
+            let data_start = offset + Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE;
+            let end = data_start + data_length + Chunk::CRC_SIZE;
+
+            if end <= bytes.len() {
+                let data = &bytes[data_start..data_start + data_length];
+                let crc = u32::from_be_bytes(
+                    bytes[end - Chunk::CRC_SIZE..end]
+                        .try_into()
+                        .expect("CRC field is exactly 4 bytes"),
+                );
+                let expected = Chunk::calculate_crc(&chunk_type, data);
+                // A bad CRC with the next header in place means damaged data, not a bad length
+                if crc == expected || end == bytes.len() || header_at(bytes, end).is_some() {
+                    if crc != expected {
+                        diagnostics.push(Diagnostic::CrcMismatch {
+                            index: chunks.len(),
+                            offset,
+                            chunk_type,
+                            expected,
+                            actual: crc,
+                        });
+                    }
+                    chunks.push(Chunk::from_raw_parts(chunk_type, data.to_vec(), crc));
+                    offsets.push(offset);
+                    offset = end;
+                    if chunk_type.bytes() == *b"IEND" {
+                        trailer = bytes[end..].to_vec();
+                        break;
+                    }
+                    continue;
+                }
+            } else {
+                resynced = resync(bytes, offset + 1);
+                if resynced.is_none() {
+                    // Nothing readable follows, so the file really ends inside this chunk
+                    let available = &bytes[data_start.min(bytes.len())..];
+                    let data = &available[..available.len().min(data_length)];
+                    diagnostics.push(Diagnostic::Truncated {
+                        index: chunks.len(),
+                        offset,
+                        chunk_type,
+                        required: end - offset,
+                        actual: bytes.len() - offset,
+                    });
+                    chunks.push(Chunk::new(chunk_type, data.to_vec()));
+                    offsets.push(offset);
+                    break;
+                }
+            }
+        }
+
+        let next = resynced
+            .or_else(|| resync(bytes, offset + 1))
+            .unwrap_or(bytes.len());
+        diagnostics.push(Diagnostic::Skipped {
+            offset,
+            length: next - offset,
+        });
+        offset = next;
+    }
+
+    let mut png = Png::from_chunks(chunks);
+    png.set_trailer(trailer);
+    ParseReport {
+        png,
+        diagnostics,
+        offsets,
+    }
+}
+
+/// Reads a plausible chunk header at `offset`: its type and data length.
+fn header_at(bytes: &[u8], offset: usize) -> Option<(ChunkType, usize)> {
+    let header = bytes.get(offset..offset + Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE)?;
+    let (length_bytes, type_bytes) = header.split_at(Chunk::LENGTH_SIZE);
+    let data_length = u32::from_be_bytes(length_bytes.try_into().ok()?) as usize;
+    if data_length > Chunk::MAX_DATA_SIZE {
+        return None;
+    }
+    let chunk_type = ChunkType::try_from(type_bytes).ok()?;
+    Some((chunk_type, data_length))
+}
+
+/// Finds the first offset at or after `from` holding a complete chunk with a valid CRC.
+fn resync(bytes: &[u8], from: usize) -> Option<usize> {
+    (from..bytes.len()).find(|&offset| {
+        let Some((chunk_type, data_length)) = header_at(bytes, offset) else {
+            return false;
+        };
+        let data_start = offset + Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE;
+        let Some(crc_bytes) =
+            bytes.get(data_start + data_length..data_start + data_length + Chunk::CRC_SIZE)
+        else {
+            return false;
+        };
+        let crc = u32::from_be_bytes(crc_bytes.try_into().expect("CRC field is exactly 4 bytes"));
+        crc == Chunk::calculate_crc(&chunk_type, &bytes[data_start..data_start + data_length])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn chunk(chunk_type: &str, data: &str) -> Chunk {
+        Chunk::new(
+            ChunkType::from_str(chunk_type).unwrap(),
+            data.as_bytes().to_vec(),
+        )
+    }
+
+    fn testing_bytes() -> Vec<u8> {
+        Png::from_chunks(vec![
+            chunk("FrSt", "I am the first chunk"),
+            chunk("miDl", "I am another chunk"),
+            chunk("LASt", "I am the last chunk"),
+        ])
+        .as_bytes()
+    }
+
+    fn parse(bytes: &[u8]) -> ParseReport {
+        Png::parse_with(bytes, ParseOptions::lenient()).unwrap()
+    }
+
+    #[test]
+    fn test_clean_input_has_no_diagnostics() {
+        let report = parse(&testing_bytes());
+        assert_eq!(report.png.chunks().len(), 3);
+        assert_eq!(report.offsets, report.png.chunk_offsets());
+        assert!(report.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_strict_rejects_damage() {
+        let mut bytes = testing_bytes();
+        bytes[20] ^= 1;
+        assert!(Png::parse_with(&bytes, ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_keeps_chunk_with_bad_crc() {
+        let mut bytes = testing_bytes();
+        bytes[20] ^= 1;
+        let report = parse(&bytes);
+        assert_eq!(report.png.chunks().len(), 3);
+        assert!(!report.png.chunks()[0].has_valid_crc());
+        assert!(matches!(
+            report.diagnostics[..],
+            [Diagnostic::CrcMismatch {
+                index: 0,
+                offset: 8,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_resyncs_after_corrupt_length() {
+        let mut bytes = testing_bytes();
+        // Corrupt the length of the second chunk so it overruns the third
+        bytes[40] = 0x7f;
+        let report = parse(&bytes);
+        let types: Vec<String> = report
+            .png
+            .chunks()
+            .iter()
+            .map(|c| c.chunk_type().to_string())
+            .collect();
+        assert_eq!(types, ["FrSt", "LASt"]);
+        assert_eq!(report.offsets, [8, 70]);
+        assert!(matches!(
+            report.diagnostics[..],
+            [Diagnostic::Skipped {
+                offset: 40,
+                length: 30
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_keeps_truncated_tail() {
+        let bytes = testing_bytes();
+        let report = parse(&bytes[..bytes.len() - 10]);
+        assert_eq!(report.png.chunks().len(), 3);
+        assert_eq!(report.png.chunks()[2].data(), b"I am the last");
+        assert!(matches!(
+            report.diagnostics[..],
+            [Diagnostic::Truncated {
+                index: 2,
+                required: 31,
+                actual: 21,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_keeps_trailer_after_iend() {
+        let mut bytes = testing_bytes();
+        bytes.extend_from_slice(&chunk("IEND", "").as_bytes());
+        bytes.extend_from_slice(b"trailing junk");
+        let report = parse(&bytes);
+        assert_eq!(report.png.chunks().len(), 4);
+        assert_eq!(report.png.trailer(), b"trailing junk");
+        assert!(report.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_missing_signature() {
+        let bytes = testing_bytes();
+        let report = parse(&bytes[8..]);
+        assert_eq!(report.png.chunks().len(), 3);
+        assert_eq!(report.offsets, [0, 32, 62]);
+        assert_eq!(report.diagnostics, [Diagnostic::BadSignature]);
+    }
+}
@@ -0,0 +1,442 @@
+use std::fmt;
+use std::path::Path;
+
+use clap::ValueEnum;
+use pngme::{
+    Chunk, ChunkType, FilePayload, Fragment, Ihdr, ItxtChunk, Png, TextChunk, ZtxtChunk,
+    encryption, fragment, payload,
+};
+use serde::Serialize;
+
+/// Output formats for `print`.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Format {
+    /// Human-readable listing
+    #[default]
+    Text,
+    /// The full report as JSON
+    Json,
+    /// The full report as YAML
+    Yaml,
+    /// One row per chunk, with a header row
+    Csv,
+}
+
+/// Machine-readable description of a PNG file, as emitted by `print --format`.
+#[derive(Serialize)]
+pub struct PngReport {
+    pub file: String,
+    /// Decoded IHDR fields, or `None` if IHDR is missing or invalid
+    pub header: Option<HeaderReport>,
+    pub chunks: Vec<ChunkReport>,
+    /// Number of bytes after IEND
+    pub trailer_size: usize,
+}
+
+#[derive(Serialize)]
+pub struct HeaderReport {
+    pub width: u32,
+    pub height: u32,
+    pub bit_depth: u8,
+    pub color_type: String,
+    pub compression_method: u8,
+    pub filter_method: u8,
+    pub interlace_method: String,
+}
+
+/// One chunk of the report; flat so it also fits a CSV row.
+#[derive(Serialize)]
+pub struct ChunkReport {
+    pub index: usize,
+    /// Byte offset of the chunk's length field in the file
+    pub offset: usize,
+    pub chunk_type: String,
+    pub critical: bool,
+    pub public: bool,
+    pub reserved_bit_valid: bool,
+    pub safe_to_copy: bool,
+    pub length: u32,
+    pub crc: String,
+    pub crc_valid: bool,
+    /// Why decoders would reject this chunk's type, if they would
+    pub warning: Option<String>,
+    /// Decoded contents for known chunk types and pngme payloads
+    pub summary: Option<String>,
+}
+
+impl PngReport {
+    pub fn new(file_path: &Path, png: &Png) -> Self {
+        let chunks = png
+            .chunks()
+            .iter()
+            .zip(png.chunk_offsets())
+            .enumerate()
+            .map(|(index, (chunk, offset))| ChunkReport::new(index, offset, chunk))
+            .collect();
+
+        Self {
+            file: file_path.display().to_string(),
+            header: png.header_info().ok().map(|ihdr| HeaderReport::from(&ihdr)),
+            chunks,
+            trailer_size: png.trailer().len(),
+        }
+    }
+}
+
+impl From<&Ihdr> for HeaderReport {
+    fn from(ihdr: &Ihdr) -> Self {
+        Self {
+            width: ihdr.width(),
+            height: ihdr.height(),
+            bit_depth: ihdr.bit_depth(),
+            color_type: ihdr.color_type().to_string(),
+            compression_method: ihdr.compression_method(),
+            filter_method: ihdr.filter_method(),
+            interlace_method: ihdr.interlace_method().to_string(),
+        }
+    }
+}
+
+impl ChunkReport {
+    fn new(index: usize, offset: usize, chunk: &Chunk) -> Self {
+        let chunk_type = chunk.chunk_type();
+        Self {
+            index,
+            offset,
+            chunk_type: chunk_type.to_string(),
+            critical: chunk_type.is_critical(),
+            public: chunk_type.is_public(),
+            reserved_bit_valid: chunk_type.is_reserved_bit_valid(),
+            safe_to_copy: chunk_type.is_safe_to_copy(),
+            length: chunk.length(),
+            crc: format!("0x{:08X}", chunk.crc()),
+            crc_valid: chunk.has_valid_crc(),
+            warning: warning(chunk_type),
+            summary: summarize(chunk),
+        }
+    }
+}
+
+/// Formats chunks as a table of type, length, property flags and warnings.
+pub fn chunk_table(chunks: &[ChunkReport]) -> String {
+    let mut table =
+        String::from("Chunk  Type  Length  Critical  Public  Reserved  Copy    Warning\n");
+    for chunk in chunks {
+        let yes_no = |flag| if flag { "yes" } else { "no" };
+        let row = format!(
+            "{:>5}  {}  {:>6}  {:<8}  {:<6}  {:<8}  {:<6}  {}",
+            chunk.index,
+            chunk.chunk_type,
+            chunk.length,
+            yes_no(chunk.critical),
+            yes_no(chunk.public),
+            if chunk.reserved_bit_valid {
+                "ok"
+            } else {
+                "SET"
+            },
+            if chunk.safe_to_copy { "safe" } else { "unsafe" },
+            chunk.warning.as_deref().unwrap_or(""),
+        );
+        table.push_str(row.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// Joins the type warnings for a chunk, or `None` if decoders will accept it.
+fn warning(chunk_type: &ChunkType) -> Option<String> {
+    let warnings: Vec<String> = chunk_type
+        .warnings()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    (!warnings.is_empty()).then(|| warnings.join("; "))
+}
+
+/// Decoded contents of a chunk, shown as one line by `print` and as a
+/// field list by `inspect`.
+pub struct Breakdown {
+    pub summary: String,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl Breakdown {
+    fn new(summary: impl Into<String>, fields: Vec<(&'static str, String)>) -> Self {
+        Self {
+            summary: summary.into(),
+            fields,
+        }
+    }
+
+    fn invalid(error: impl fmt::Display) -> Self {
+        Self::new(
+            format!("invalid: {error}"),
+            vec![("Error", error.to_string())],
+        )
+    }
+}
+
+/// Describes the contents of standard chunks and of pngme's own payloads.
+///
+/// Returns `None` for chunks with nothing useful to show, such as IDAT or IEND.
+pub fn summarize(chunk: &Chunk) -> Option<String> {
+    breakdown(chunk).map(|breakdown| breakdown.summary)
+}
+
+/// Decodes known chunk types and pngme payloads into a summary and named fields.
+///
+/// Returns `None` for types without a known layout.
+pub fn breakdown(chunk: &Chunk) -> Option<Breakdown> {
+    let data = chunk.data();
+    let breakdown = match &chunk.chunk_type().bytes() {
+        b"IHDR" => match Ihdr::try_from(chunk) {
+            Ok(ihdr) => Breakdown::new(
+                ihdr.to_string(),
+                vec![
+                    ("Width", ihdr.width().to_string()),
+                    ("Height", ihdr.height().to_string()),
+                    ("Bit depth", ihdr.bit_depth().to_string()),
+                    (
+                        "Color type",
+                        format!("{} ({})", ihdr.color_type() as u8, ihdr.color_type()),
+                    ),
+                    ("Compression", ihdr.compression_method().to_string()),
+                    ("Filter", ihdr.filter_method().to_string()),
+                    (
+                        "Interlace",
+                        format!(
+                            "{} ({})",
+                            ihdr.interlace_method() as u8,
+                            ihdr.interlace_method()
+                        ),
+                    ),
+                ],
+            ),
+            Err(e) => Breakdown::invalid(e),
+        },
+        b"PLTE" => {
+            let entries = data.len() / 3;
+            Breakdown::new(
+                format!("{entries} palette entries"),
+                vec![("Entries", entries.to_string())],
+            )
+        }
+        b"tEXt" => match TextChunk::try_from(chunk) {
+            Ok(text) => Breakdown::new(
+                format!("{}: {}", text.keyword(), text.text()),
+                vec![
+                    ("Keyword", text.keyword().to_string()),
+                    ("Text", text.text().to_string()),
+                ],
+            ),
+            Err(e) => Breakdown::invalid(e),
+        },
+        b"zTXt" => match ZtxtChunk::try_from(chunk) {
+            Ok(ztxt) => Breakdown::new(
+                format!(
+                    "{}: {} bytes uncompressed",
+                    ztxt.keyword(),
+                    ztxt.uncompressed_len()
+                ),
+                vec![
+                    ("Keyword", ztxt.keyword().to_string()),
+                    ("Compression", "0 (zlib deflate)".to_string()),
+                    (
+                        "Compressed size",
+                        format!("{} bytes", ztxt.compressed_len()),
+                    ),
+                    (
+                        "Uncompressed size",
+                        format!("{} bytes", ztxt.uncompressed_len()),
+                    ),
+                    ("Text", ztxt.text().to_string()),
+                ],
+            ),
+            Err(e) => Breakdown::invalid(e),
+        },
+        b"iTXt" => match ItxtChunk::try_from(chunk) {
+            Ok(itxt) => Breakdown::new(
+                match itxt.language_tag() {
+                    "" => format!("{}: {}", itxt.keyword(), itxt.text()),
+                    tag => format!("{} [{tag}]: {}", itxt.keyword(), itxt.text()),
+                },
+                vec![
+                    ("Keyword", itxt.keyword().to_string()),
+                    ("Compressed", itxt.is_compressed().to_string()),
+                    ("Language", itxt.language_tag().to_string()),
+                    ("Translated keyword", itxt.translated_keyword().to_string()),
+                    ("Text", itxt.text().to_string()),
+                ],
+            ),
+            Err(e) => Breakdown::invalid(e),
+        },
+        b"gAMA" => match data.try_into() {
+            Ok(bytes) => {
+                let gamma = u32::from_be_bytes(bytes);
+                let value = gamma as f64 / 100_000.0;
+                Breakdown::new(
+                    format!("gamma {value:.5}"),
+                    vec![("Gamma", format!("{gamma} ({value:.5})"))],
+                )
+            }
+            Err(_) => Breakdown::invalid("expected 4 bytes"),
+        },
+        b"sRGB" => match data {
+            &[intent] => {
+                let name = match intent {
+                    0 => "perceptual",
+                    1 => "relative colorimetric",
+                    2 => "saturation",
+                    3 => "absolute colorimetric",
+                    _ => return Some(Breakdown::invalid(format!("rendering intent {intent}"))),
+                };
+                Breakdown::new(
+                    format!("{name} rendering intent"),
+                    vec![("Rendering intent", format!("{intent} ({name})"))],
+                )
+            }
+            _ => Breakdown::invalid("expected 1 byte"),
+        },
+        b"pHYs" => match data {
+            [x @ .., unit] if x.len() == 8 => {
+                let x_ppu = u32::from_be_bytes(x[..4].try_into().expect("4 bytes"));
+                let y_ppu = u32::from_be_bytes(x[4..].try_into().expect("4 bytes"));
+                let (summary, unit) = match unit {
+                    1 => (
+                        format!("{x_ppu}x{y_ppu} pixels per metre"),
+                        "1 (metre)".to_string(),
+                    ),
+                    unit => (
+                        format!("{x_ppu}:{y_ppu} pixel aspect ratio"),
+                        format!("{unit} (unknown, aspect ratio only)"),
+                    ),
+                };
+                Breakdown::new(
+                    summary,
+                    vec![
+                        ("Pixels per unit, X", x_ppu.to_string()),
+                        ("Pixels per unit, Y", y_ppu.to_string()),
+                        ("Unit", unit),
+                    ],
+                )
+            }
+            _ => Breakdown::invalid("expected 9 bytes"),
+        },
+        b"tIME" => match data {
+            [y0, y1, month, day, hour, minute, second] => {
+                let year = u16::from_be_bytes([*y0, *y1]);
+                Breakdown::new(
+                    format!("{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02} UTC"),
+                    vec![
+                        ("Year", year.to_string()),
+                        ("Month", month.to_string()),
+                        ("Day", day.to_string()),
+                        ("Hour", hour.to_string()),
+                        ("Minute", minute.to_string()),
+                        ("Second", second.to_string()),
+                    ],
+                )
+            }
+            _ => Breakdown::invalid("expected 7 bytes"),
+        },
+        _ if fragment::is_fragment(data) => match Fragment::try_from(data) {
+            Ok(f) => Breakdown::new(
+                format!(
+                    "fragment {} of {} of payload {}",
+                    f.index() + 1,
+                    f.total(),
+                    f.payload_id()
+                ),
+                vec![
+                    ("Payload", "pngme fragment".to_string()),
+                    ("Payload id", f.payload_id().to_string()),
+                    ("Fragment", format!("{} of {}", f.index() + 1, f.total())),
+                    ("Fragment data", format!("{} bytes", f.data().len())),
+                ],
+            ),
+            Err(e) => Breakdown::invalid(e),
+        },
+        _ if encryption::is_encrypted(data) => {
+            let cost = |at: usize| {
+                data.get(at..at + 4)
+                    .map(|b| u32::from_be_bytes(b.try_into().expect("4 bytes")).to_string())
+                    .unwrap_or_else(|| "?".to_string())
+            };
+            let magic = encryption::MAGIC.len();
+            Breakdown::new(
+                "encrypted payload",
+                vec![
+                    ("Payload", "pngme encrypted data".to_string()),
+                    (
+                        "Version",
+                        data.get(magic).map_or("?".to_string(), u8::to_string),
+                    ),
+                    ("Argon2 memory", format!("{} KiB", cost(magic + 1))),
+                    ("Argon2 passes", cost(magic + 5)),
+                    ("Argon2 lanes", cost(magic + 9)),
+                    (
+                        "Ciphertext",
+                        format!(
+                            "{} bytes",
+                            data.len()
+                                .saturating_sub(encryption::HEADER_SIZE + encryption::TAG_SIZE)
+                        ),
+                    ),
+                ],
+            )
+        }
+        _ if payload::is_file_payload(data) => match FilePayload::try_from(data) {
+            Ok(file) => Breakdown::new(
+                format!("file '{}' ({} bytes)", file.file_name(), file.data().len()),
+                vec![
+                    ("Payload", "pngme file".to_string()),
+                    ("File name", file.file_name().to_string()),
+                    ("File size", format!("{} bytes", file.data().len())),
+                ],
+            ),
+            Err(e) => Breakdown::invalid(e),
+        },
+        _ => return None,
+    };
+    Some(breakdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_breakdown_summary_and_fields_agree() {
+        let chunk = Chunk::new(
+            ChunkType::from_str("pHYs").unwrap(),
+            vec![0, 0, 0x0b, 0x13, 0, 0, 0x0b, 0x13, 1],
+        );
+        let breakdown = breakdown(&chunk).unwrap();
+        assert_eq!(breakdown.summary, "2835x2835 pixels per metre");
+        assert_eq!(
+            breakdown.fields,
+            [
+                ("Pixels per unit, X", "2835".to_string()),
+                ("Pixels per unit, Y", "2835".to_string()),
+                ("Unit", "1 (metre)".to_string()),
+            ]
+        );
+        assert_eq!(summarize(&chunk).unwrap(), breakdown.summary);
+    }
+
+    #[test]
+    fn test_breakdown_invalid_and_unknown() {
+        let chunk = Chunk::new(ChunkType::from_str("sRGB").unwrap(), vec![7]);
+        let breakdown = breakdown(&chunk).unwrap();
+        assert_eq!(breakdown.summary, "invalid: rendering intent 7");
+        assert_eq!(
+            breakdown.fields,
+            [("Error", "rendering intent 7".to_string())]
+        );
+
+        let chunk = Chunk::new(ChunkType::from_str("IDAT").unwrap(), vec![1, 2, 3]);
+        assert!(super::breakdown(&chunk).is_none());
+    }
+}
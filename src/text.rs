@@ -0,0 +1,363 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+use flate2::Compression;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+
+/// Specialized `Result` type for text chunk operations.
+pub type Result<T> = std::result::Result<T, TextError>;
+
+/// A decoded PNG tEXt chunk: a Latin-1 keyword and its Latin-1 text.
+///
+/// On the wire the chunk data is laid out as:
+/// - 1-79 bytes: Keyword (Latin-1)
+/// - 1 byte: Null separator
+/// - N bytes: Text (Latin-1, not null-terminated)
+///
+/// # Examples
+///
+/// ```ignore
+/// let text = TextChunk::new("Comment", "Meet me at midnight")?;
+/// png.insert_before_iend(text.to_chunk())?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextChunk {
+    keyword: String,
+    text: String,
+}
+
+/// Errors that can occur when building or parsing text chunks.
+#[derive(Debug, thiserror::Error)]
+pub enum TextError {
+    /// Returned when the chunk passed in is not of the expected text type.
+    #[error("Expected {expected} chunk, got '{actual}'")]
+    WrongChunkType {
+        expected: &'static str,
+        actual: ChunkType,
+    },
+
+    /// Returned when a keyword is empty or longer than 79 bytes.
+    #[error("Keyword must be 1-79 characters, got {0}")]
+    InvalidKeywordLength(usize),
+
+    /// Returned when a keyword starts or ends with a space or contains
+    /// consecutive spaces.
+    #[error("Keyword '{0}' has leading, trailing or consecutive spaces")]
+    InvalidKeywordSpacing(String),
+
+    /// Returned when a keyword contains a character outside printable Latin-1.
+    #[error("Keyword contains invalid character {0:?}")]
+    InvalidKeywordChar(char),
+
+    /// Returned when text contains a character that cannot be stored as Latin-1.
+    #[error("Text contains character {0:?} that is not Latin-1")]
+    NotLatin1(char),
+
+    /// Returned when text contains a null byte.
+    #[error("Text must not contain null characters")]
+    NullInText,
+
+    /// Returned when the chunk data has no null separator after the keyword.
+    #[error("Missing null separator after keyword")]
+    MissingSeparator,
+
+    /// Returned when the chunk data ends before a required field.
+    #[error("Text chunk data is truncated")]
+    Truncated,
+
+    /// Returned when an iTXt compression flag is neither 0 nor 1.
+    #[error("Invalid compression flag {0}")]
+    InvalidCompressionFlag(u8),
+
+    /// Returned when the compression method byte is not 0 (deflate).
+    #[error("Unknown compression method {0}")]
+    UnknownCompressionMethod(u8),
+
+    /// Returned when a language tag is not a valid RFC 3066 tag.
+    #[error("Invalid language tag '{0}'")]
+    InvalidLanguageTag(String),
+
+    /// Returned when UTF-8 text fields contain invalid sequences.
+    #[error("Invalid UTF-8 in text chunk")]
+    InvalidUtf8(#[from] std::string::FromUtf8Error),
+
+    /// Returned when the compressed text cannot be inflated.
+    #[error("Failed to decompress text")]
+    Decompression(#[source] std::io::Error),
+
+    /// Returned when compressed text inflates past the size limit.
+    #[error("Decompressed text exceeds the {0}-byte limit")]
+    DecompressedTooLarge(usize),
+}
+
+/// Largest text that compressed chunks may inflate to.
+///
+/// A few bytes of deflate can expand to gigabytes, so the limit keeps a
+/// crafted chunk from exhausting memory.
+pub const MAX_DECOMPRESSED_LENGTH: usize = 16 * 1024 * 1024;
+
+impl TextChunk {
+    /// Maximum keyword length in bytes allowed by the PNG specification.
+    pub const MAX_KEYWORD_LENGTH: usize = 79;
+
+    /// Creates a new tEXt entry after validating the keyword and text.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TextChunk)` - If the keyword is valid and the text is Latin-1
+    /// * `Err(TextError)` - If either fails validation
+    pub fn new(keyword: &str, text: &str) -> Result<Self> {
+        validate_keyword(keyword)?;
+        validate_latin1(text)?;
+
+        Ok(Self {
+            keyword: keyword.to_string(),
+            text: text.to_string(),
+        })
+    }
+
+    /// The keyword identifying this entry, such as "Title" or "Comment".
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    /// The text stored under the keyword.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Serializes the entry into a new tEXt chunk.
+    pub fn to_chunk(&self) -> Chunk {
+        let mut data = encode_latin1(&self.keyword).expect("keyword validated as Latin-1");
+        data.push(0);
+        data.extend(encode_latin1(&self.text).expect("text validated as Latin-1"));
+        Chunk::new(
+            ChunkType::from_str("tEXt").expect("tEXt is a valid chunk type"),
+            data,
+        )
+    }
+}
+
+/// Attempts to decode a tEXt chunk.
+///
+/// The keyword is validated; the text is accepted as any Latin-1 bytes.
+impl TryFrom<&Chunk> for TextChunk {
+    type Error = TextError;
+
+    fn try_from(chunk: &Chunk) -> Result<Self> {
+        expect_chunk_type(chunk, "tEXt")?;
+        let (keyword, text) = split_keyword(chunk.data())?;
+
+        Ok(Self {
+            keyword,
+            text: decode_latin1(text),
+        })
+    }
+}
+
+impl fmt::Display for TextChunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.keyword, self.text)
+    }
+}
+
+/// Checks a keyword against the PNG specification.
+///
+/// Keywords must be 1-79 printable Latin-1 characters (32-126 and 161-255)
+/// with no leading, trailing or consecutive spaces.
+pub fn validate_keyword(keyword: &str) -> Result<()> {
+    let length = keyword.chars().count();
+    if length == 0 || length > TextChunk::MAX_KEYWORD_LENGTH {
+        return Err(TextError::InvalidKeywordLength(length));
+    }
+
+    if let Some(c) = keyword
+        .chars()
+        .find(|&c| !matches!(c as u32, 32..=126 | 161..=255))
+    {
+        return Err(TextError::InvalidKeywordChar(c));
+    }
+
+    if keyword.starts_with(' ') || keyword.ends_with(' ') || keyword.contains("  ") {
+        return Err(TextError::InvalidKeywordSpacing(keyword.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Checks that text can be stored in a tEXt or zTXt chunk: Latin-1 with no nulls.
+pub(crate) fn validate_latin1(text: &str) -> Result<()> {
+    validate_no_null(text)?;
+    encode_latin1(text).map(|_| ())
+}
+
+/// Returns an error if text contains a null, which separates text chunk fields.
+pub(crate) fn validate_no_null(text: &str) -> Result<()> {
+    if text.contains('\0') {
+        return Err(TextError::NullInText);
+    }
+    Ok(())
+}
+
+/// Returns an error unless the chunk has the given type.
+pub(crate) fn expect_chunk_type(chunk: &Chunk, expected: &'static str) -> Result<()> {
+    if chunk.chunk_type().bytes() != expected.as_bytes() {
+        return Err(TextError::WrongChunkType {
+            expected,
+            actual: *chunk.chunk_type(),
+        });
+    }
+    Ok(())
+}
+
+/// Splits chunk data at the first null byte into a validated keyword and the rest.
+pub(crate) fn split_keyword(data: &[u8]) -> Result<(String, &[u8])> {
+    let separator = data
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or(TextError::MissingSeparator)?;
+    let keyword = decode_latin1(&data[..separator]);
+    validate_keyword(&keyword)?;
+    Ok((keyword, &data[separator + 1..]))
+}
+
+/// Deflates bytes into a zlib stream, the only compression method PNG defines.
+pub(crate) fn compress(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(bytes)
+        .expect("writing to a Vec cannot fail");
+    encoder.finish().expect("writing to a Vec cannot fail")
+}
+
+/// Inflates a zlib stream of at most [`MAX_DECOMPRESSED_LENGTH`] bytes.
+pub(crate) fn decompress(bytes: &[u8]) -> Result<Vec<u8>> {
+    decompress_limited(bytes, MAX_DECOMPRESSED_LENGTH)
+}
+
+/// Inflates a zlib stream, failing once it passes `limit` bytes.
+fn decompress_limited(bytes: &[u8], limit: usize) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    ZlibDecoder::new(bytes)
+        .take(limit as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(TextError::Decompression)?;
+    if decompressed.len() > limit {
+        return Err(TextError::DecompressedTooLarge(limit));
+    }
+    Ok(decompressed)
+}
+
+/// Encodes a string as Latin-1, failing on characters above U+00FF.
+pub(crate) fn encode_latin1(s: &str) -> Result<Vec<u8>> {
+    s.chars()
+        .map(|c| u8::try_from(c).map_err(|_| TextError::NotLatin1(c)))
+        .collect()
+}
+
+/// Decodes Latin-1 bytes; every byte maps directly to the code point of the same value.
+pub(crate) fn decode_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| char::from(b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_chunk_round_trip() {
+        let text = TextChunk::new("Comment", "Caf\u{e9} at midnight").unwrap();
+        let chunk = text.to_chunk();
+        assert_eq!(&chunk.chunk_type().to_string(), "tEXt");
+        assert_eq!(chunk.data(), b"Comment\0Caf\xe9 at midnight");
+
+        let parsed = TextChunk::try_from(&chunk).unwrap();
+        assert_eq!(parsed, text);
+        assert_eq!(parsed.keyword(), "Comment");
+        assert_eq!(parsed.text(), "Caf\u{e9} at midnight");
+    }
+
+    #[test]
+    fn test_keyword_validation() {
+        assert!(validate_keyword("Title").is_ok());
+        assert!(validate_keyword(&"a".repeat(79)).is_ok());
+        assert!(matches!(
+            validate_keyword(""),
+            Err(TextError::InvalidKeywordLength(0))
+        ));
+        assert!(matches!(
+            validate_keyword(&"a".repeat(80)),
+            Err(TextError::InvalidKeywordLength(80))
+        ));
+        assert!(matches!(
+            validate_keyword(" Title"),
+            Err(TextError::InvalidKeywordSpacing(_))
+        ));
+        assert!(matches!(
+            validate_keyword("Title "),
+            Err(TextError::InvalidKeywordSpacing(_))
+        ));
+        assert!(matches!(
+            validate_keyword("My  Title"),
+            Err(TextError::InvalidKeywordSpacing(_))
+        ));
+        assert!(matches!(
+            validate_keyword("Tab\tbed"),
+            Err(TextError::InvalidKeywordChar('\t'))
+        ));
+    }
+
+    #[test]
+    fn test_text_not_latin1() {
+        assert!(matches!(
+            TextChunk::new("Comment", "\u{2603}"),
+            Err(TextError::NotLatin1('\u{2603}'))
+        ));
+    }
+
+    #[test]
+    fn test_validate_latin1() {
+        assert!(validate_latin1("Caf\u{e9}").is_ok());
+        assert!(matches!(
+            validate_latin1("a\0b"),
+            Err(TextError::NullInText)
+        ));
+        assert!(matches!(
+            validate_latin1("\u{2603}"),
+            Err(TextError::NotLatin1('\u{2603}'))
+        ));
+    }
+
+    #[test]
+    fn test_text_chunk_missing_separator() {
+        let chunk = Chunk::new(ChunkType::from_str("tEXt").unwrap(), b"Comment".to_vec());
+        assert!(matches!(
+            TextChunk::try_from(&chunk),
+            Err(TextError::MissingSeparator)
+        ));
+    }
+
+    #[test]
+    fn test_decompress_limit() {
+        let bomb = compress(&[0; 1001]);
+        assert_eq!(decompress_limited(&bomb[..], 1001).unwrap().len(), 1001);
+        assert!(matches!(
+            decompress_limited(&bomb[..], 1000),
+            Err(TextError::DecompressedTooLarge(1000))
+        ));
+    }
+
+    #[test]
+    fn test_text_chunk_wrong_type() {
+        let chunk = Chunk::new(ChunkType::from_str("ruSt").unwrap(), b"a\0b".to_vec());
+        assert!(matches!(
+            TextChunk::try_from(&chunk),
+            Err(TextError::WrongChunkType { .. })
+        ));
+    }
+}
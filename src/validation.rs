@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::chunk::Chunk;
+
+/// Chunk types that the PNG specification allows at most once per file.
+pub const UNIQUE_CHUNK_TYPES: [&str; 14] = [
+    "IHDR", "PLTE", "IEND", "cHRM", "gAMA", "iCCP", "sBIT", "sRGB", "bKGD", "hIST", "tRNS", "pHYs",
+    "tIME", "eXIf",
+];
+
+/// A structural violation of the PNG specification's chunk ordering rules.
+///
+/// Indices refer to positions in `Png::chunks()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The PNG contains no chunks at all.
+    NoChunks,
+
+    /// No IHDR chunk is present.
+    MissingIhdr,
+
+    /// IHDR is present but is not the first chunk.
+    IhdrNotFirst { index: usize },
+
+    /// No IDAT chunk is present.
+    MissingIdat,
+
+    /// IDAT chunks are separated by other chunks.
+    ///
+    /// `index` is the first IDAT that follows a non-IDAT chunk after the IDAT run began.
+    NonContiguousIdat { index: usize },
+
+    /// PLTE appears after the first IDAT chunk.
+    PlteAfterIdat { index: usize },
+
+    /// No IEND chunk is present.
+    MissingIend,
+
+    /// IEND is present but is not the last chunk.
+    IendNotLast { index: usize },
+
+    /// A chunk type that may appear only once is repeated.
+    DuplicateChunk {
+        chunk_type: &'static str,
+        index: usize,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::NoChunks => write!(f, "PNG contains no chunks"),
+            Violation::MissingIhdr => write!(f, "IHDR chunk is missing"),
+            Violation::IhdrNotFirst { index } => {
+                write!(f, "Chunk {index}: IHDR must be the first chunk")
+            }
+            Violation::MissingIdat => write!(f, "IDAT chunk is missing"),
+            Violation::NonContiguousIdat { index } => {
+                write!(f, "Chunk {index}: IDAT chunks must be consecutive")
+            }
+            Violation::PlteAfterIdat { index } => {
+                write!(f, "Chunk {index}: PLTE must precede the first IDAT")
+            }
+            Violation::MissingIend => write!(f, "IEND chunk is missing"),
+            Violation::IendNotLast { index } => {
+                write!(f, "Chunk {index}: IEND must be the last chunk")
+            }
+            Violation::DuplicateChunk { chunk_type, index } => {
+                write!(f, "Chunk {index}: {chunk_type} may only appear once")
+            }
+        }
+    }
+}
+
+/// Checks a chunk list against the structural rules of the PNG specification.
+///
+/// Returns every violation found, ordered by the check that produced it.
+/// An empty vector means the chunk layout is valid.
+pub(crate) fn validate_chunks(chunks: &[Chunk]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    if chunks.is_empty() {
+        violations.push(Violation::NoChunks);
+        return violations;
+    }
+
+    let is_type =
+        |chunk: &Chunk, chunk_type: &str| chunk.chunk_type().bytes() == chunk_type.as_bytes();
+
+    // IHDR must come first
+    match chunks.iter().position(|c| is_type(c, "IHDR")) {
+        None => violations.push(Violation::MissingIhdr),
+        Some(0) => {}
+        Some(index) => violations.push(Violation::IhdrNotFirst { index }),
+    }
+
+    // IDAT chunks must exist and form a single consecutive run
+    let first_idat = chunks.iter().position(|c| is_type(c, "IDAT"));
+    match first_idat {
+        None => violations.push(Violation::MissingIdat),
+        Some(first) => {
+            let mut run_ended = false;
+            for (index, chunk) in chunks.iter().enumerate().skip(first) {
+                if !is_type(chunk, "IDAT") {
+                    run_ended = true;
+                } else if run_ended {
+                    violations.push(Violation::NonContiguousIdat { index });
+                    break;
+                }
+            }
+        }
+    }
+
+    // PLTE must precede the image data
+    if let Some(first) = first_idat {
+        for (index, chunk) in chunks.iter().enumerate().skip(first) {
+            if is_type(chunk, "PLTE") {
+                violations.push(Violation::PlteAfterIdat { index });
+            }
+        }
+    }
+
+    // IEND must come last
+    let last = chunks.len() - 1;
+    match chunks.iter().position(|c| is_type(c, "IEND")) {
+        None => violations.push(Violation::MissingIend),
+        Some(index) if index != last => violations.push(Violation::IendNotLast { index }),
+        Some(_) => {}
+    }
+
+    // Unique chunks may appear at most once
+    for chunk_type in UNIQUE_CHUNK_TYPES {
+        chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| is_type(c, chunk_type))
+            .skip(1)
+            .for_each(|(index, _)| {
+                violations.push(Violation::DuplicateChunk { chunk_type, index })
+            });
+    }
+
+    violations
+}
@@ -0,0 +1,150 @@
+use std::io::Write;
+
+use crate::{
+    chunk::{CRC, Chunk, ChunkError},
+    chunk_type::ChunkType,
+    png::{Png, PngError, Result},
+};
+
+/// Writes a PNG stream one chunk at a time.
+///
+/// The signature is written when the writer is created. Chunks go straight
+/// to the underlying stream, so memory use does not grow with the image.
+/// Wrap files in a `BufWriter` and call [`PngWriter::finish`] to flush it.
+///
+/// # Examples
+///
+/// ```ignore
+/// let file = BufWriter::new(File::create("out.png")?);
+/// let mut writer = PngWriter::new(file)?;
+/// for chunk in png.chunks() {
+///     writer.write_chunk(chunk)?;
+/// }
+/// writer.finish()?;
+/// ```
+pub struct PngWriter<W: Write> {
+    writer: W,
+    /// Bytes written to the stream so far
+    offset: usize,
+}
+
+impl<W: Write> PngWriter<W> {
+    /// Wraps a stream and writes the PNG signature to it.
+    pub fn new(mut writer: W) -> Result<Self> {
+        writer.write_all(&Png::STANDARD_HEADER)?;
+        Ok(Self {
+            writer,
+            offset: Png::STANDARD_HEADER.len(),
+        })
+    }
+
+    /// Number of bytes written, including the signature.
+    ///
+    /// This is the offset at which the next chunk will start.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Writes a chunk exactly as stored, including its CRC.
+    pub fn write_chunk(&mut self, chunk: &Chunk) -> Result<()> {
+        self.write_fields(chunk.chunk_type(), chunk.data(), Some(chunk.crc()))
+    }
+
+    /// Writes a chunk from its type and data, computing the CRC as the data is written.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the chunk was written
+    /// * `Err(PngError::InvalidChunk)` - If `data` exceeds the PNG size limit
+    /// * `Err(PngError::Io)` - If writing fails
+    pub fn write_chunk_data(&mut self, chunk_type: &ChunkType, data: &[u8]) -> Result<()> {
+        self.write_fields(chunk_type, data, None)
+    }
+
+    /// Writes raw bytes after the last chunk, such as a [trailer](Png::trailer).
+    pub fn write_trailer(&mut self, trailer: &[u8]) -> Result<()> {
+        self.writer.write_all(trailer)?;
+        self.offset += trailer.len();
+        Ok(())
+    }
+
+    /// Flushes the stream and returns it.
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Writes the four chunk fields, computing the CRC alongside the data if none is given.
+    fn write_fields(
+        &mut self,
+        chunk_type: &ChunkType,
+        data: &[u8],
+        crc: Option<u32>,
+    ) -> Result<()> {
+        if data.len() > Chunk::MAX_DATA_SIZE {
+            return Err(PngError::InvalidChunk {
+                position: self.offset,
+                source: ChunkError::TooLarge { size: data.len() },
+            });
+        }
+
+        let mut digest = CRC.digest();
+        self.writer.write_all(&(data.len() as u32).to_be_bytes())?;
+        self.writer.write_all(&chunk_type.bytes())?;
+        self.writer.write_all(data)?;
+        let crc = crc.unwrap_or_else(|| {
+            digest.update(&chunk_type.bytes());
+            digest.update(data);
+            digest.finalize()
+        });
+        self.writer.write_all(&crc.to_be_bytes())?;
+        self.offset += Chunk::LENGTH_SIZE + Chunk::TYPE_SIZE + data.len() + Chunk::CRC_SIZE;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn testing_png() -> Png {
+        Png::from_chunks(vec![
+            Chunk::new(ChunkType::from_str("FrSt").unwrap(), b"first".to_vec()),
+            Chunk::new(ChunkType::from_str("LASt").unwrap(), b"last".to_vec()),
+        ])
+    }
+
+    #[test]
+    fn test_writes_same_bytes_as_png() {
+        let png = testing_png();
+        let mut writer = PngWriter::new(Vec::new()).unwrap();
+        for chunk in png.chunks() {
+            writer.write_chunk(chunk).unwrap();
+        }
+        assert_eq!(writer.offset(), png.as_bytes().len());
+        assert_eq!(writer.finish().unwrap(), png.as_bytes());
+    }
+
+    #[test]
+    fn test_write_chunk_data_computes_crc() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let mut writer = PngWriter::new(Vec::new()).unwrap();
+        writer.write_chunk_data(&chunk_type, b"hidden").unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let expected = Chunk::new(chunk_type, b"hidden".to_vec()).as_bytes();
+        assert_eq!(&bytes[..8], &Png::STANDARD_HEADER);
+        assert_eq!(&bytes[8..], &expected[..]);
+    }
+
+    #[test]
+    fn test_output_parses_back() {
+        let png = testing_png();
+        let mut bytes = Vec::new();
+        png.write_to(&mut bytes).unwrap();
+        let parsed = Png::try_from(&bytes[..]).unwrap();
+        assert_eq!(parsed.chunks().len(), 2);
+        assert_eq!(parsed.chunks()[1].data(), b"last");
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::text::{self, Result, TextError};
+
+/// A decoded PNG zTXt chunk: a Latin-1 keyword and deflate-compressed Latin-1 text.
+///
+/// On the wire the chunk data is laid out as:
+/// - 1-79 bytes: Keyword (Latin-1)
+/// - 1 byte: Null separator
+/// - 1 byte: Compression method (always 0, zlib deflate)
+/// - N bytes: Compressed text (zlib stream)
+///
+/// The text is held both inflated and as its zlib stream: [`ZtxtChunk::new`]
+/// compresses it once and parsing keeps the stream as read, so
+/// [`ZtxtChunk::compressed_len`] is the size actually stored in the file.
+///
+/// # Examples
+///
+/// ```ignore
+/// let ztxt = ZtxtChunk::new("Comment", &long_message)?;
+/// png.insert_before_iend(ztxt.to_chunk())?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZtxtChunk {
+    keyword: String,
+    text: String,
+    compressed: Vec<u8>,
+}
+
+impl ZtxtChunk {
+    /// The only compression method defined by the PNG specification (zlib deflate).
+    pub const COMPRESSION_METHOD: u8 = 0;
+
+    /// Creates a new zTXt entry after validating the keyword and text.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZtxtChunk)` - If the keyword is valid and the text is Latin-1
+    /// * `Err(TextError)` - If either fails validation
+    pub fn new(keyword: &str, text: &str) -> Result<Self> {
+        text::validate_keyword(keyword)?;
+        text::validate_latin1(text)?;
+
+        Ok(Self {
+            keyword: keyword.to_string(),
+            text: text.to_string(),
+            compressed: text::compress(
+                &text::encode_latin1(text).expect("text validated as Latin-1"),
+            ),
+        })
+    }
+
+    /// The keyword identifying this entry, such as "Title" or "Comment".
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    /// The uncompressed text stored under the keyword.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Length of the text in bytes before compression.
+    pub fn uncompressed_len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Length of the compressed text stream in bytes.
+    pub fn compressed_len(&self) -> usize {
+        self.compressed.len()
+    }
+
+    /// Serializes the entry into a new zTXt chunk.
+    pub fn to_chunk(&self) -> Chunk {
+        let mut data = text::encode_latin1(&self.keyword).expect("keyword validated as Latin-1");
+        data.push(0);
+        data.push(Self::COMPRESSION_METHOD);
+        data.extend_from_slice(&self.compressed);
+        Chunk::new(
+            ChunkType::from_str("zTXt").expect("zTXt is a valid chunk type"),
+            data,
+        )
+    }
+}
+
+/// Attempts to decode a zTXt chunk, inflating its text.
+impl TryFrom<&Chunk> for ZtxtChunk {
+    type Error = TextError;
+
+    fn try_from(chunk: &Chunk) -> Result<Self> {
+        text::expect_chunk_type(chunk, "zTXt")?;
+        let (keyword, rest) = text::split_keyword(chunk.data())?;
+
+        let (&method, compressed) = rest.split_first().ok_or(TextError::Truncated)?;
+        if method != Self::COMPRESSION_METHOD {
+            return Err(TextError::UnknownCompressionMethod(method));
+        }
+
+        Ok(Self {
+            keyword,
+            text: text::decode_latin1(&text::decompress(compressed)?),
+            compressed: compressed.to_vec(),
+        })
+    }
+}
+
+impl fmt::Display for ZtxtChunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.keyword, self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ztxt_chunk_round_trip() {
+        let message = "All work and no play makes Jack a dull boy. ".repeat(50);
+        let ztxt = ZtxtChunk::new("Comment", &message).unwrap();
+        let chunk = ztxt.to_chunk();
+        assert_eq!(&chunk.chunk_type().to_string(), "zTXt");
+        assert!((chunk.length() as usize) < ztxt.uncompressed_len());
+        assert!(chunk.data().starts_with(b"Comment\0\0"));
+
+        let parsed = ZtxtChunk::try_from(&chunk).unwrap();
+        assert_eq!(parsed, ztxt);
+        assert_eq!(parsed.text(), message);
+    }
+
+    #[test]
+    fn test_ztxt_compressed_len() {
+        let ztxt = ZtxtChunk::new("Comment", &"la ".repeat(100)).unwrap();
+        let chunk = ztxt.to_chunk();
+        assert_eq!(
+            ztxt.compressed_len(),
+            chunk.data().len() - b"Comment\0\0".len()
+        );
+        assert!(ztxt.compressed_len() < ztxt.uncompressed_len());
+
+        // A parsed entry reports the stream as stored, padding included
+        let mut data = b"Comment\0\0".to_vec();
+        data.extend(text::compress(b"hi"));
+        data.extend_from_slice(&[0; 4]);
+        let chunk = Chunk::new(ChunkType::from_str("zTXt").unwrap(), data);
+        let parsed = ZtxtChunk::try_from(&chunk).unwrap();
+        assert_eq!(parsed.text(), "hi");
+        assert_eq!(
+            parsed.compressed_len(),
+            chunk.data().len() - b"Comment\0\0".len()
+        );
+    }
+
+    #[test]
+    fn test_ztxt_unknown_compression_method() {
+        let chunk = Chunk::new(
+            ChunkType::from_str("zTXt").unwrap(),
+            b"Comment\0\x01abc".to_vec(),
+        );
+        assert!(matches!(
+            ZtxtChunk::try_from(&chunk),
+            Err(TextError::UnknownCompressionMethod(1))
+        ));
+    }
+
+    #[test]
+    fn test_ztxt_corrupt_stream() {
+        let chunk = Chunk::new(
+            ChunkType::from_str("zTXt").unwrap(),
+            b"Comment\0\0not zlib".to_vec(),
+        );
+        assert!(matches!(
+            ZtxtChunk::try_from(&chunk),
+            Err(TextError::Decompression(_))
+        ));
+
+        let chunk = Chunk::new(ChunkType::from_str("zTXt").unwrap(), b"Comment\0".to_vec());
+        assert!(matches!(
+            ZtxtChunk::try_from(&chunk),
+            Err(TextError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn test_ztxt_null_in_text() {
+        assert!(matches!(
+            ZtxtChunk::new("Comment", "a\0b"),
+            Err(TextError::NullInText)
+        ));
+    }
+}